@@ -25,6 +25,8 @@
 //! along one wall. The only door is locked, but it includes a small
 //! interface. "Restricted Area - Strictly No Digitized Users Allowed."
 
+use ::ParseError;
+
 /// Finds the sum of all digits that match the digit offset by `offset` in the
 /// list.
 ///
@@ -147,8 +149,27 @@ pub fn part2(input: &str) -> u32 {
     count_matching(input, input.len() / 2)
 }
 
-pub fn parse_input(input: &str) -> &str {
-    &input[..input.len() - 1]
+pub fn parse_input(input: &str) -> Result<&str, ParseError> {
+    let digits = input.trim_end();
+
+    match digits.find(|c: char| !c.is_ascii_digit()) {
+        Some(i) => Err(ParseError::at(input, i, "a digit")),
+        None => Ok(digits),
+    }
 }
 
-test_day!("01", 1341, 1348);
+solution!(&'a str);
+
+test_examples!(
+    ("1122\n", 1, 3u32),
+    ("1111\n", 1, 4u32),
+    ("1234\n", 1, 0u32),
+    ("91212129\n", 1, 9u32),
+    ("1212\n", 2, 6u32),
+    ("1221\n", 2, 0u32),
+    ("123425\n", 2, 4u32),
+    ("123123\n", 2, 12u32),
+    ("12131415\n", 2, 4u32),
+);
+
+test_day!(2017, "01", 1341, 1348);
@@ -5,6 +5,9 @@
 //! repair the corruption in this spreadsheet - if we take another
 //! millisecond, we'll have to display an hourglass cursor!"
 
+use ::parse;
+use ::ParseError;
+
 /// The spreadsheet consists of rows of apparently-random numbers. To make
 /// sure the recovery process is on the right track, they need you to
 /// calculate the spreadsheet's *checksum*. For each row, determine the
@@ -33,7 +36,7 @@
 /// # 7\t5\t3
 /// # 2\t4\t6\t8
 /// # ";
-/// assert_eq!(part1(&parse_input(input)), 18);
+/// assert_eq!(part1(&parse_input(input).unwrap()), 18);
 /// ```
 ///
 /// *What is the checksum* for the spreadsheet in your puzzle input?
@@ -86,7 +89,7 @@ pub fn part1<'a, I, J>(lines: I) -> usize
 /// # 9\t4\t7\t3
 /// # 3\t8\t6\t5
 /// # ";
-/// assert_eq!(part2(&parse_input(input)), 9);
+/// assert_eq!(part2(&parse_input(input).unwrap()), 9);
 /// ```
 ///
 /// What is the *sum of each row's result* in your puzzle input?
@@ -114,13 +117,20 @@ pub fn part2<'a, I, J>(lines: I) -> usize
 }
 
 /// Parses input into a grid of numbers.
-pub fn parse_input(input: &str) -> Vec<Vec<usize>> {
+pub fn parse_input(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
     input.lines()
         .map(|line| line.split('\t')
-            .map(|x| x.parse::<usize>().expect("Unexpected non-integer in spreadsheet"))
-            .collect::<Vec<_>>()
+            .map(|x| parse::token(input, x, "an integer"))
+            .collect::<Result<Vec<_>, _>>()
         )
-        .collect::<Vec<_>>()
+        .collect()
 }
 
-test_day!("02", 34925, 221);
+solution!(Vec<Vec<usize>>);
+
+test_examples!(
+    ("5\t1\t9\t5\n7\t5\t3\n2\t4\t6\t8\n", 1, 18usize),
+    ("5\t9\t2\t8\n9\t4\t7\t3\n3\t8\t6\t5\n", 2, 9usize),
+);
+
+test_day!(2017, "02", 34925, 221);
@@ -19,6 +19,10 @@
 use ::itertools::Itertools;
 use ::Direction;
 use ::Direction::*;
+use ::grid::{ Hashed, InfiniteGrid };
+use ::Point2;
+use ::parse;
+use ::ParseError;
 
 static DIRECTIONS: [Direction; 4] = [Right, Up, Left, Down];
 
@@ -77,11 +81,11 @@ fn spiral() -> impl Iterator<Item=&'static ::Direction> {
 ///
 ///   [Manhattan Distance]: https://en.wikipedia.org/wiki/Taxicab_geometry
 pub fn part1(index: &usize) -> usize {
-    let (x, y) = spiral()
+    let position = spiral()
         .take(index - 1)
-        .fold((0isize, 0isize), |pos, facing| pos + facing);
+        .fold(Point2::new(0isize, 0), |pos, &facing| pos + facing);
 
-    (x.abs() + y.abs()) as usize
+    position.manhattan(Point2::default()) as usize
 }
 
 /// As a stress test on the system, the programs here clear the grid and
@@ -123,27 +127,22 @@ pub fn part1(index: &usize) -> usize {
 /// assert!(stress_test().take(23).eq(solution.iter().cloned()));
 /// ```
 pub fn stress_test() -> impl Iterator<Item=usize> {
-    use std::collections::HashMap;
-
-    let mut cache = HashMap::new();
-    cache.insert((0, 0), 1);
+    let mut cache = InfiniteGrid::<usize, Hashed<usize>>::new();
+    cache[(0, 0)] = 1;
 
     ::std::iter::once(1)
         .chain(spiral()
-            .scan(((0, 0), cache), |state, &facing| {
+            .scan((Point2::new(0, 0), cache), |state, &facing| {
                 (state.0) += facing;
 
                 let val = ::std::cmp::max(
                     1,
                     NEIGHBORS.iter()
-                        .map(|&(x, y)| state.1
-                            .get(&((state.0).0 + x, (state.0).1 + y))
-                            .unwrap_or(&0)
-                        )
+                        .map(|&offset| state.1[state.0 + Point2::from(offset)])
                         .sum::<usize>(),
                 );
 
-                state.1.insert(state.0, val);
+                state.1[state.0] = val;
 
                 Some(val)
             })
@@ -159,10 +158,21 @@ pub fn part2(index: &usize) -> usize {
         .expect("Found no solution for step 2")
 }
 
-pub fn parse_input(input: &str) -> usize {
-    input[..input.len() - 1]
-        .parse::<usize>()
-        .expect("Unexpected non-integer")
+pub fn parse_input(input: &str) -> Result<usize, ParseError> {
+    parse::token(input, input.trim_end(), "an integer")
 }
 
-test_day!("03", 430, 312453);
+solution!(usize);
+
+test_examples!(
+    ("1\n", 1, 0usize),
+    ("12\n", 1, 3usize),
+    ("23\n", 1, 2usize),
+    ("1024\n", 1, 31usize),
+    ("3\n", 2, 4usize),
+    ("6\n", 2, 10usize),
+    ("100\n", 2, 122usize),
+    ("800\n", 2, 806usize),
+);
+
+test_day!(2017, "03", 430, 312453);
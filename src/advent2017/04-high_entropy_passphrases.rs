@@ -4,6 +4,8 @@
 //! use a *passphrase* instead of simply a pass*word*. A passphrase consists
 //! of a series of words (lowercase letters) separated by spaces.
 
+use ::ParseError;
+
 /// To ensure security, a valid passphrase must contain no duplicate words.
 ///
 /// For example:
@@ -116,8 +118,21 @@ pub fn part2(input: &str) -> usize {
         .count()
 }
 
-pub fn parse_input(input: &str) -> &str {
-    input
+pub fn parse_input(input: &str) -> Result<&str, ParseError> {
+    Ok(input)
 }
 
-test_day!("04", 477, 167);
+solution!(&'a str);
+
+test_examples!(
+    ("aa bb cc dd ee\n", 1, 1usize),
+    ("aa bb cc dd aa\n", 1, 0usize),
+    ("aa bb cc dd aaa\n", 1, 1usize),
+    ("abcde fghij\n", 2, 1usize),
+    ("abcde xyz ecdab\n", 2, 0usize),
+    ("a ab abc abd abf abj\n", 2, 1usize),
+    ("iiii oiii ooii oooi oooo\n", 2, 1usize),
+    ("oiii ioii iioi iiio\n", 2, 0usize),
+);
+
+test_day!(2017, "04", 477, 167);
@@ -6,15 +6,18 @@
 //! cycles to help find the exit.
 //!
 
+use ::parse;
+use ::ParseError;
+
 /// The message includes a list of the offsets for each jump. Jumps are
 /// relative: `-1` moves to the previous instruction, and `2` skips the next
 /// one. Start at the first instruction in the list. The goal is to follow
 /// the jumps until one leads *outside* the list.
-pub fn parse_input(input: &str) -> Vec<isize> {
+pub fn parse_input(input: &str) -> Result<Vec<isize>, ParseError> {
     input
         .split_terminator('\n')
-        .map(|x| x.parse::<isize>().expect("Unexpected non-integer jump"))
-        .collect::<Vec<_>>()
+        .map(|x| parse::token(input, x, "an integer jump"))
+        .collect()
 }
 
 /// In addition, these instructions are a little strange; after each jump,
@@ -57,7 +60,7 @@ pub fn parse_input(input: &str) -> Vec<isize> {
 /// # 0
 /// # 1
 /// # -3
-/// # ");
+/// # ").unwrap();
 /// assert_eq!(part1(&input), 5);
 /// ```
 ///
@@ -83,7 +86,7 @@ pub fn part1(jumps: &Vec<isize>) -> usize {
 /// # 0
 /// # 1
 /// # -3
-/// # ");
+/// # ").unwrap();
 /// assert_eq!(part2(&input), 10);
 /// ```
 ///
@@ -112,4 +115,11 @@ pub fn count_steps<F: Fn(isize) -> isize>(mut memory: Vec<isize>, mut_fn: F) ->
     + 1
 }
 
-test_day!("05", 360603, 25347697);
+solution!(Vec<isize>);
+
+test_examples!(
+    ("0\n3\n0\n1\n-3\n", 1, 5usize),
+    ("0\n3\n0\n1\n-3\n", 2, 10usize),
+);
+
+test_day!(2017, "05", 360603, 25347697);
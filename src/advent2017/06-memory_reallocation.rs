@@ -4,16 +4,19 @@
 //! memory reallocation routine, but it keeps getting stuck in an infinite
 //! loop.
 
+use ::parse;
+use ::ParseError;
+
 /// In this area, there are <span title="There are also five currency banks,
 /// two river banks, three airplanes banking, a banked billards shot, and a
 /// left bank.">sixteen memory banks</span>; each memory bank can hold any
 /// number of *blocks*. The goal of the reallocation routine is to balance the
 /// blocks between the memory banks.
-pub fn parse_input(input: &str) -> Vec<usize> {
-    input[..input.len() - 1]
+pub fn parse_input(input: &str) -> Result<Vec<usize>, ParseError> {
+    input.trim_end()
         .split('\t')
-        .map(|x| x.parse::<usize>().expect("Unexpected non-integer number of block"))
-        .collect::<Vec<_>>()
+        .map(|x| parse::token(input, x, "an integer number of blocks"))
+        .collect()
 }
 
 /// The reallocation routine operates in cycles. In each cycle, it finds the
@@ -54,7 +57,7 @@ pub fn parse_input(input: &str) -> Vec<usize> {
 ///
 /// ```
 /// # use advent_solutions::advent2017::day06::{ parse_input, part1 };
-/// # let input = parse_input("0\t2\t7\t0\n");
+/// # let input = parse_input("0\t2\t7\t0\n").unwrap();
 /// assert_eq!(part1(&input), 5);
 /// ```
 ///
@@ -107,7 +110,7 @@ pub fn part1(banks: &Vec<usize>) -> usize {
 ///
 /// ```
 /// # use advent_solutions::advent2017::day06::{ parse_input, part2 };
-/// # let input = parse_input("0\t2\t7\t0\n");
+/// # let input = parse_input("0\t2\t7\t0\n").unwrap();
 /// assert_eq!(part2(&input), 4);
 /// ```
 ///
@@ -148,4 +151,11 @@ pub fn part2(banks: &Vec<usize>) -> usize {
     }
 }
 
-test_day!("06", 11137, 1037);
+solution!(Vec<usize>);
+
+test_examples!(
+    ("0\t2\t7\t0\n", 1, 5usize),
+    ("0\t2\t7\t0\n", 2, 4usize),
+);
+
+test_day!(2017, "06", 11137, 1037);
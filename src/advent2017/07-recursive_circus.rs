@@ -16,6 +16,8 @@
 use ::std::collections::HashMap;
 use ::nom::digit;
 use ::parse::{ name as parse_name };
+use ::parse;
+use ::ParseError;
 
 /// You offer to help, but first you need to understand the structure of
 /// these towers. You ask each program to yell out their *name*, their
@@ -271,10 +273,32 @@ pub fn part2(nodes: &[Node]) -> usize {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Node> {
-    Node::list_from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing nodes")
+pub fn parse_input(input: &str) -> Result<Vec<Node>, ParseError> {
+    parse::complete(input, Node::list_from_bytes(input.as_bytes()), "a program")
 }
 
-test_day!("07", "mkxke", 268);
+solution!(Vec<Node<'a>>);
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+pbga (66)
+xhth (57)
+ebii (61)
+havc (66)
+ktlj (57)
+fwft (72) -> ktlj, cntj, xhth
+qoyq (66)
+padx (45) -> pbga, havc, qoyq
+tknk (41) -> ugml, padx, fwft
+jptl (61)
+ugml (68) -> gyxo, ebii, jptl
+gyxo (61)
+cntj (57)
+";
+
+test_examples!(
+    (EXAMPLE, 1, "tknk"),
+    (EXAMPLE, 2, 60usize),
+);
+
+test_day!(2017, "07", "mkxke", 268);
@@ -10,6 +10,8 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 use ::parse::{ name as parse_name, signed_number as parse_signed_number };
+use ::parse;
+use ::ParseError;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 enum Action { Inc, Dec }
@@ -208,10 +210,23 @@ pub fn part2(instructions: &[Instruction]) -> isize {
     max_reg_value
 }
 
-pub fn parse_input(input: &str) -> Vec<Instruction> {
-    Instruction::list_from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing instructions")
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::complete(input, Instruction::list_from_bytes(input.as_bytes()), "an instruction")
 }
 
-test_day!("08", 4647, 5590);
+solution!(Vec<Instruction<'a>>);
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+b inc 5 if a > 1
+a inc 1 if b < 5
+c dec -10 if a >= 1
+c inc -20 if c == 10
+";
+
+test_examples!(
+    (EXAMPLE, 1, 1isize),
+    (EXAMPLE, 2, 10isize),
+);
+
+test_day!(2017, "08", 4647, 5590);
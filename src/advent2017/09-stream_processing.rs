@@ -8,6 +8,8 @@
 //! of characters*.
 
 use nom::anychar;
+use ::parse;
+use ::ParseError;
 
 /// You sit for a while and record part of the stream (your puzzle input).
 /// The characters represent *groups* - sequences that begin with `{` and
@@ -309,10 +311,28 @@ pub fn part2(root: &Node) -> usize {
     root.count_garbage()
 }
 
-pub fn parse_input(input: &str) -> Node {
-    Node::from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing stream")
+pub fn parse_input(input: &str) -> Result<Node, ParseError> {
+    parse::complete(input, Node::from_bytes(input.as_bytes()), "a group")
 }
 
-test_day!("09", 14204, 6622);
+solution!(Node);
+
+test_examples!(
+    ("{}\n", 1, 1usize),
+    ("{{{}}}\n", 1, 6usize),
+    ("{{},{}}\n", 1, 5usize),
+    ("{{{},{},{{}}}}\n", 1, 16usize),
+    ("{<a>,<a>,<a>,<a>}\n", 1, 1usize),
+    ("{{<ab>},{<ab>},{<ab>},{<ab>}}\n", 1, 9usize),
+    ("{{<!!>},{<!!>},{<!!>},{<!!>}}\n", 1, 9usize),
+    ("{{<a!>},{<a!>},{<a!>},{<ab>}}\n", 1, 3usize),
+    ("<>\n", 2, 0usize),
+    ("<random characters>\n", 2, 17usize),
+    ("<<<<>\n", 2, 3usize),
+    ("<{!>}>\n", 2, 2usize),
+    ("<!!>\n", 2, 0usize),
+    ("<!!!>>\n", 2, 0usize),
+    ("<{o\"i!a,<{i<a>\n", 2, 10usize),
+);
+
+test_day!(2017, "09", 14204, 6622);
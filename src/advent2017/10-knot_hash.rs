@@ -24,22 +24,37 @@
 //! See [knot_hash](../knot_hash/index.html)
 
 use super::knot_hash;
+use ::parse;
+use ::ParseError;
+
+/// The *lengths* the input lists. Only part 1 reads them: part 2 takes the
+/// input as any string.
+fn lengths(input: &str) -> Result<Vec<u8>, ParseError> {
+    if input.is_empty() {
+        return Ok(vec![]);
+    }
+
+    input.split(',')
+        .map(|length| parse::token(input, length, "a length up to 255"))
+        .collect()
+}
 
 /// However, you should instead use the standard list size of `256` (with
 /// values `0` to `255`) and the sequence of *lengths* in your puzzle input.
 /// Once this process is complete, *what is the result of multiplying the
 /// first two numbers in the list*?
-pub fn part1(input: &str) -> u16 {
-    let lengths = input
-        .split(',')
-        .map(|l| l.parse::<u8>().expect("Unexpected non-u8 length"))
-        .collect::<Vec<_>>();
+pub fn part1_with_max(input: &str, max_num: u8) -> u16 {
+    let lengths = lengths(input).unwrap_or_else(|e| panic!("{}", e));
 
-    let hash = knot_hash::hash_lengths(255, &lengths, 1);
+    let hash = knot_hash::hash_lengths(max_num, &lengths, 1);
 
     (hash[0] as u16) * (hash[1] as u16)
 }
 
+pub fn part1(input: &str) -> u16 {
+    part1_with_max(input, 255)
+}
+
 /// Finally, the standard way to represent a Knot Hash is as a single
 /// [hexadecimal] string; the final output is the dense hash in hexadecimal
 /// notation. Because each number in your dense hash will be between `0` and
@@ -92,8 +107,18 @@ pub fn part2(input: &str) -> String {
         .collect::<String>()
 }
 
-pub fn parse_input(input: &str) -> &str {
-    &input[..input.len() - 1]
+pub fn parse_input(input: &str) -> Result<&str, ParseError> {
+    Ok(input.trim_end())
 }
 
-test_day!("10", 4480, "c500ffe015c83b60fad2e4b7d59dabc4");
+solution!(&'a str);
+
+test_examples!(
+    ("3,4,1,5\n", |input| part1_with_max(input, 4), 12),
+    ("\n", 2, "a2582a3a0e66e6e86e3812dcb672a272"),
+    ("AoC 2017\n", 2, "33efeb34ea91902bb2f59c9920caa6cd"),
+    ("1,2,3\n", 2, "3efbe78a8d82f29979031a4aa0b16a9d"),
+    ("1,2,4\n", 2, "63960835bcdc130f0b66d7ff4f6a5a8e"),
+);
+
+test_day!(2017, "10", 4480, "c500ffe015c83b60fad2e4b7d59dabc4");
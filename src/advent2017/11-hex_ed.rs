@@ -11,6 +11,8 @@
 use std::str::FromStr;
 use std::cmp;
 use std::ops::*;
+use ::parse;
+use ::ParseError;
 
 /// The hexagons ("hexes") in <span title="Raindrops on roses and whiskers on
 /// kittens.">this grid</span> are aligned such that adjacent hexes can be
@@ -26,7 +28,7 @@ use std::ops::*;
 ///       / s  \
 /// ```
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
-enum HexDirection { N, NE, SE, S, SW, NW }
+pub enum HexDirection { N, NE, SE, S, SW, NW }
 
 use self::HexDirection::*;
 
@@ -88,29 +90,29 @@ impl Add<HexDirection> for HexCoord {
 /// -   `ne,ne,ne` is `3` steps away.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day11::solve;
-/// assert_eq!(solve("ne,ne,ne").1, 3);
+/// # use advent_solutions::advent2017::day11::{ parse_input, solve };
+/// assert_eq!(solve(&parse_input("ne,ne,ne").unwrap()).0, 3);
 /// ```
 ///
 /// -   `ne,ne,sw,sw` is `0` steps away (back where you started).
 ///
 /// ```
-/// # use advent_solutions::advent2017::day11::solve;
-/// assert_eq!(solve("ne,ne,sw,sw").1, 0);
+/// # use advent_solutions::advent2017::day11::{ parse_input, solve };
+/// assert_eq!(solve(&parse_input("ne,ne,sw,sw").unwrap()).0, 0);
 /// ```
 ///
 /// -   `ne,ne,s,s` is `2` steps away (`se,se`).
 ///
 /// ```
-/// # use advent_solutions::advent2017::day11::solve;
-/// assert_eq!(solve("ne,ne,s,s").1, 2);
+/// # use advent_solutions::advent2017::day11::{ parse_input, solve };
+/// assert_eq!(solve(&parse_input("ne,ne,s,s").unwrap()).0, 2);
 /// ```
 ///
 /// -   `se,sw,se,sw,sw` is `3` steps away (`s,s,sw`).
 ///
 /// ```
-/// # use advent_solutions::advent2017::day11::solve;
-/// assert_eq!(solve("se,sw,se,sw,sw").1, 3);
+/// # use advent_solutions::advent2017::day11::{ parse_input, solve };
+/// assert_eq!(solve(&parse_input("se,sw,se,sw,sw").unwrap()).0, 3);
 /// ```
 ///
 ///   [hex grid]: https://en.wikipedia.org/wiki/Hexagonal_tiling
@@ -119,21 +121,33 @@ impl Add<HexDirection> for HexCoord {
 ///
 /// *How many steps away* is the *furthest* he ever got from his starting
 /// position?
-pub fn solve(input: &str) -> (usize, usize) {
-    let (max_distance, final_position) = input.split(',')
-        .map(|dir| FromStr::from_str(dir).expect("Unknown direction"))
-        .fold((0, HexCoord(0, 0)), |(furthest, coord), x| {
+pub fn solve(directions: &[HexDirection]) -> (usize, usize) {
+    let (max_distance, final_position) = directions.iter()
+        .fold((0, HexCoord(0, 0)), |(furthest, coord), &x| {
             let new_coord = coord + x;
             let distance = new_coord.distance(&HexCoord(0, 0));
 
             (cmp::max(distance, furthest), new_coord)
         });
 
-    (max_distance, final_position.distance(&HexCoord(0, 0)))
+    (final_position.distance(&HexCoord(0, 0)), max_distance)
 }
 
-pub fn parse_input(input: &str) -> &str {
-    &input[..input.len() - 1]
+pub fn parse_input(input: &str) -> Result<Vec<HexDirection>, ParseError> {
+    input.trim_end()
+        .split(',')
+        .map(|direction| parse::token(input, direction, "a direction"))
+        .collect()
 }
 
-test_day_both!("11", 1501, 759);
+solution_both!(Vec<HexDirection>);
+
+test_examples!(
+    ("ne,ne,ne\n", 1, 3usize),
+    ("ne,ne,sw,sw\n", 1, 0usize),
+    ("ne,ne,s,s\n", 1, 2usize),
+    ("se,sw,se,sw,sw\n", 1, 3usize),
+    ("ne,ne,sw,sw\n", 2, 2usize),
+);
+
+test_day_both!(2017, "11", 759, 1501);
@@ -23,6 +23,8 @@
 
 use std::collections::{ HashMap, HashSet };
 use ::parse::unsigned_number;
+use ::parse;
+use ::ParseError;
 
 named!{ parse_connection (&[u8]) -> (usize, Vec<usize>),
     do_parse!(
@@ -159,10 +161,26 @@ pub fn part2(connections: &HashMap<usize, Vec<usize>>) -> usize {
     groups
 }
 
-pub fn parse_input(input: &str) -> HashMap<usize, Vec<usize>> {
-    parse_connections(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing connections")
+pub fn parse_input(input: &str) -> Result<HashMap<usize, Vec<usize>>, ParseError> {
+    parse::complete(input, parse_connections(input.as_bytes()), "a connection")
 }
 
-test_day!("12", 175, 213);
+solution!(HashMap<usize, Vec<usize>>);
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+0 <-> 2
+1 <-> 1
+2 <-> 0, 3, 4
+3 <-> 2, 4
+4 <-> 2, 3, 6
+5 <-> 6
+6 <-> 4, 5
+";
+
+test_examples!(
+    (EXAMPLE, 1, 6usize),
+    (EXAMPLE, 2, 2usize),
+);
+
+test_day!(2017, "12", 175, 213);
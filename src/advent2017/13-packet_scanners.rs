@@ -4,8 +4,10 @@
 //! layers, each with a *security scanner* that moves back and forth across
 //! the layer. To succeed, you must not be detected by a scanner.
 
-use std::str::FromStr;
 use std::ops;
+use ::parse::unsigned_number;
+use ::parse;
+use ::ParseError;
 
 /// Within each layer, a security scanner moves back and forth within its
 /// range. Each security scanner starts at the top and moves down until it
@@ -50,6 +52,10 @@ pub struct Layer {
 }
 
 impl Layer {
+    fn new(range: usize) -> Layer {
+        Layer { range, position: 0, forward: true }
+    }
+
     fn step(&mut self) {
         if self.forward {
             if self.position < self.range - 1 {
@@ -69,18 +75,6 @@ impl Layer {
     }
 }
 
-impl FromStr for Layer {
-    type Err = ::std::num::ParseIntError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Layer {
-            range: s.parse::<usize>()?,
-            position: 0,
-            forward: true,
-        })
-    }
-}
-
 #[cfg(test)]
 #[test]
 fn test_layers() {
@@ -89,7 +83,7 @@ fn test_layers() {
 4: 4
 6: 4
 ";
-    let mut firewall = Firewall::new(&input);
+    let mut firewall = parse_input(input).unwrap();
 
     // Picosecond 0
     assert_eq!(firewall[0].unwrap().position, 0);
@@ -151,19 +145,16 @@ fn test_layers() {
 pub struct Firewall(Vec<Option<Layer>>);
 
 impl Firewall {
-    fn new(input: &str) -> Firewall {
+    /// From `(depth, range)` pairs.
+    fn new(layers: &[(usize, usize)]) -> Firewall {
         Firewall(
-            input.lines()
-            .fold(Vec::new(), |mut firewall, line| {
-                let mut vals = line.split(": ");
-                let depth: usize = vals.next().map(FromStr::from_str).unwrap().unwrap();
-                let layer = vals.next().map(FromStr::from_str).unwrap().unwrap();
-
+            layers.iter()
+            .fold(Vec::new(), |mut firewall, &(depth, range)| {
                 if firewall.len() < depth + 1 {
                     firewall.resize(depth + 1, None);
                 }
 
-                firewall[depth] = Some(layer);
+                firewall[depth] = Some(Layer::new(range));
                 firewall
             })
         )
@@ -201,7 +192,7 @@ fn test_firewall() {
 4: 4
 6: 4
 ";
-    let firewall = Firewall::new(&input);
+    let firewall = parse_input(input).unwrap();
 
     assert_eq!(firewall.len(), 7);
     assert_eq!(firewall[0].unwrap().range, 3);
@@ -341,19 +332,19 @@ fn test_firewall() {
 /// the example above, the trip severity is `0*3 + 6*4 = 24`.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day13::part1;
+/// # use advent_solutions::advent2017::day13::{ parse_input, part1 };
 /// # let input = "0: 3
 /// # 1: 2
 /// # 4: 4
 /// # 6: 4
 /// # ";
-/// assert_eq!(part1(&input), 24);
+/// assert_eq!(part1(&parse_input(input).unwrap()), 24);
 /// ```
 ///
 /// Given the details of the firewall you've recorded, if you leave
 /// immediately, *what is the severity of your whole trip*?
-pub fn part1(input: &str) -> usize {
-    let mut firewall = Firewall::new(&input);
+pub fn part1(firewall: &Firewall) -> usize {
+    let mut firewall = firewall.clone();
     let mut severity = 0;
 
     for depth in 0..firewall.len() {
@@ -492,20 +483,18 @@ pub fn part1(input: &str) -> usize {
 /// picoseconds you would need to delay to get through safely is `10`.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day13::part2;
+/// # use advent_solutions::advent2017::day13::{ parse_input, part2 };
 /// # let input = "0: 3
 /// # 1: 2
 /// # 4: 4
 /// # 6: 4
 /// # ";
-/// assert_eq!(part2(&input), 10);
+/// assert_eq!(part2(&parse_input(input).unwrap()), 10);
 /// ```
 ///
 /// *What is the fewest number of picoseconds* that you need to delay the
 /// packet to pass through the firewall without being caught?
-pub fn part2(input: &str) -> usize {
-    let firewall = Firewall::new(&input);
-
+pub fn part2(firewall: &Firewall) -> usize {
     for delay in 0.. {
         let caught = firewall.0.iter()
             .enumerate()
@@ -522,8 +511,31 @@ pub fn part2(input: &str) -> usize {
     unreachable!();
 }
 
-pub fn parse_input(input: &str) -> &str {
-    input
+named!{ parse_layer (&[u8]) -> (usize, usize),
+    do_parse!(
+        depth: unsigned_number >>
+        tag!(": ") >>
+        // Scanners need room to move.
+        range: verify!(unsigned_number, |range| range >= 2) >>
+
+        ((depth, range))
+    )
+}
+
+named!{ parse_layers (&[u8]) -> Vec<(usize, usize)>,
+    lines!(parse_layer)
 }
 
-test_day!("13", 648, 3933124);
+pub fn parse_input(input: &str) -> Result<Firewall, ParseError> {
+    parse::complete(input, parse_layers(input.as_bytes()), "a layer")
+        .map(|layers| Firewall::new(&layers))
+}
+
+solution!(Firewall);
+
+test_examples!(
+    ("0: 3\n1: 2\n4: 4\n6: 4\n", 1, 24usize),
+    ("0: 3\n1: 2\n4: 4\n6: 4\n", 2, 10usize),
+);
+
+test_day!(2017, "13", 648, 3933124);
@@ -9,30 +9,32 @@
 //!   [disk defragmenter]: https://en.wikipedia.org/wiki/Defragmentation
 //!   [sit and watch it for a while]: https://www.youtube.com/watch?v=kPv1gQ5Rs8A&t=37
 
-use std::fmt;
 use super::knot_hash;
+use ::grid::Grid;
+use ::ParseError;
 
 /// The disk in question consists of a 128x128 grid; each square of the grid
 /// is either *free* or *used*. On this disk, the state of the grid is
 /// tracked by the bits in a sequence of [knot hashes].
 ///
 ///   [knot hashes]: ../day10/index.html
-pub struct Grid(Vec<Vec<bool>>);
-
-impl fmt::Display for Grid {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.0.iter()
-            .map(|line| {
-                line.iter().map(|&x| write!(f, "{}", if x { '#' } else { '.' }))
-                    .skip_while(|x| x.is_ok())
-                    .next()
-                    .unwrap_or(Ok(()))
-                    .and_then(|_| write!(f, "\n"))
-            })
-            .skip_while(|x| x.is_ok())
-            .next()
-            .unwrap_or(Ok(()))
-            .and_then(|_| write!(f, "\n"))
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Square { Free, Used }
+
+use self::Square::*;
+
+impl From<bool> for Square {
+    fn from(used: bool) -> Self {
+        if used { Used } else { Free }
+    }
+}
+
+impl From<Square> for char {
+    fn from(square: Square) -> Self {
+        match square {
+            Free => '.',
+            Used => '#',
+        }
     }
 }
 
@@ -141,42 +143,34 @@ pub fn part2<'a, I, J>(hashes: I) -> usize
     where I: IntoIterator<Item=J>,
           J: IntoIterator<Item=&'a u8>,
 {
-    let grid = Grid(
+    let grid = Grid::from_rows(
         hashes.into_iter()
             .map(|row| row.into_iter()
                 .flat_map(|x| ::iter::Bits::new(*x as usize, 8))
+                .map(Square::from)
                 .collect()
             )
             .collect()
-    );
+    ).expect("Hashes of different lengths");
 
     use std::collections::HashSet;
 
-    let mut ungrouped = grid.0.iter()
-        .enumerate()
-        .flat_map(|(y, row)| ::std::iter::repeat(y)
-            .zip(0..row.len())
-        )
-        .filter(|&(y, x)| grid.0[y][x])
+    let mut ungrouped = grid.positions()
+        .filter(|&position| grid[position] == Used)
         .collect::<HashSet<(usize, usize)>>();
 
     let mut groups = Vec::new();
 
-    while let Some(&(y, x)) = ungrouped.iter().next() {
+    while let Some(&position) = ungrouped.iter().next() {
         let mut group = Vec::new();
 
-        let mut candidates = vec![(y, x)];
-
-        while let Some((y, x)) = candidates.pop() {
-            if ungrouped.contains(&(y, x)) {
-                group.push((y, x));
+        let mut candidates = vec![position];
 
-                if y < 127 { candidates.push((y + 1, x)); }
-                if y > 0   { candidates.push((y - 1, x)); }
-                if x < 127 { candidates.push((y, x + 1)); }
-                if x > 0   { candidates.push((y, x - 1)); }
+        while let Some(position) = candidates.pop() {
+            if ungrouped.remove(&position) {
+                group.push(position);
 
-                ungrouped.remove(&(y, x));
+                candidates.extend(grid.neighbors4(position).map(|(_, neighbor)| neighbor));
             }
         }
 
@@ -186,8 +180,15 @@ pub fn part2<'a, I, J>(hashes: I) -> usize
     groups.len()
 }
 
-pub fn parse_input(input: &str) -> Vec<Vec<u8>> {
-    make_hashes(&input[..input.len() - 1])
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u8>>, ParseError> {
+    Ok(make_hashes(input.trim_end()))
 }
 
-test_day!("14", 8222, 1086);
+solution!(Vec<Vec<u8>>);
+
+test_examples!(
+    ("flqrgnkx\n", 1, 8108u32),
+    ("flqrgnkx\n", 2, 1242usize),
+);
+
+test_day!(2017, "14", 8222, 1086);
@@ -10,6 +10,12 @@
 //! value, compares the lowest 16 bits of both values, and keeps track of
 //! the number of times those parts of the values match.
 
+use ::parse;
+use ::ParseError;
+
+/// The `(factor, starting value)` of generators A and B.
+pub type Generators = ((u32, u32), (u32, u32));
+
 /// The generators both work on the same principle. To create its next
 /// value, a generator will take the previous value it produced, multiply it
 /// by a *factor* (generator A uses `16807`; generator B uses `48271`), and
@@ -99,7 +105,7 @@ impl Iterator for Generator {
 /// ```
 ///
 /// After 40 million pairs, *what is the judge's final count*?
-pub fn part1(&((a_f, a_s), (b_f, b_s)): &((u32, u32), (u32, u32))) -> usize {
+pub fn part1(&((a_f, a_s), (b_f, b_s)): &Generators) -> usize {
     Generator::new(a_f, a_s)
         .zip(Generator::new(b_f, b_s))
         .take(40_000_000)
@@ -192,7 +198,7 @@ pub fn part1(&((a_f, a_s), (b_f, b_s)): &((u32, u32), (u32, u32))) -> usize {
 ///
 /// After 5 million pairs, but using this new generator logic, *what is the
 /// judge's final count*?
-pub fn part2(&((a_f, a_s), (b_f, b_s)): &((u32, u32), (u32, u32))) -> usize {
+pub fn part2(&((a_f, a_s), (b_f, b_s)): &Generators) -> usize {
     Generator::new(a_f, a_s)
         .filter(|x| x % 4 == 0)
         .zip(Generator::new(b_f, b_s)
@@ -203,15 +209,38 @@ pub fn part2(&((a_f, a_s), (b_f, b_s)): &((u32, u32), (u32, u32))) -> usize {
         .count()
 }
 
-pub fn parse_input(input: &str) -> ((u32, u32), (u32, u32)) {
-    let mut starts = input.lines()
-        .map(|line| line[24..].parse::<u32>().expect("Could not parse input"));
+pub fn parse_input(input: &str) -> Result<Generators, ParseError> {
+    let mut lines = input.lines();
+
+    let mut start = |generator| {
+        let line = lines.next()
+            .ok_or_else(|| ParseError::at(input, input.len(), generator))?;
 
-    let a_s = starts.next().expect("Could not find a_s");
-    let b_s = starts.next().expect("Could not find b_s");
-    assert!(starts.next().is_none(), "More than 2 lines");
+        // `Generator X starts with N`
+        parse::token(input, line.rsplit(' ').next().unwrap(), "a starting value")
+    };
 
-    ((16807, a_s), (48271, b_s))
+    let a_s = start("generator A")?;
+    let b_s = start("generator B")?;
+
+    if let Some(line) = lines.next() {
+        return Err(ParseError::at_token(input, line, "the end of the input"));
+    }
+
+    Ok(((16807, a_s), (48271, b_s)))
 }
 
-test_day!("15", 567, 323);
+solution!(Generators);
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+Generator A starts with 65
+Generator B starts with 8921
+";
+
+test_examples!(
+    (EXAMPLE, 1, 588usize),
+    (EXAMPLE, 2, 309usize),
+);
+
+test_day!(2017, "15", 567, 323);
@@ -5,16 +5,17 @@
 
 use nom::anychar;
 use ::parse::unsigned_number;
+use ::parse;
+use ::ParseError;
 
 /// There are sixteen programs in total, named `a` through `p`. They start
 /// by standing in a <span title="This is called a 'newline'.">line</span>:
 /// `a` stands in position `0`, `b` stands in position `1`, and so on until
 /// `p`, which stands in position `15`.
-fn initial_programs() -> Vec<char> {
-    vec![
-        'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h',
-        'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p',
-    ]
+fn initial_programs(count: usize) -> Vec<char> {
+    (0..count as u8)
+        .map(|i| (b'a' + i) as char)
+        .collect()
 }
 
 /// The programs' *dance* consists of a sequence of *dance moves*:
@@ -161,14 +162,18 @@ fn dance(mut programs: Vec<char>, moves: &[Move]) -> Vec<char> {
 /// You watch the dance for a while and record their dance moves (your
 /// puzzle input). *In what order are the programs standing* after their
 /// dance?
-pub fn part1(moves: &[Move]) -> String {
-    dance(initial_programs(), moves)
+pub fn part1_with_programs(moves: &[Move], count: usize) -> String {
+    dance(initial_programs(count), moves)
         .into_iter().collect()
 }
 
-fn find_cycle(moves: &[Move]) -> usize {
-    let programs = initial_programs();
-    let mut p = initial_programs();
+pub fn part1(moves: &[Move]) -> String {
+    part1_with_programs(moves, 16)
+}
+
+fn find_cycle(moves: &[Move], count: usize) -> usize {
+    let programs = initial_programs(count);
+    let mut p = initial_programs(count);
 
     for i in 1.. {
         p = dance(p, moves);
@@ -196,10 +201,10 @@ fn find_cycle(moves: &[Move]) -> usize {
 /// -   `pe/b`, swapping programs `e` and `b`: `ceadb`.
 ///
 /// *In what order are the programs standing* after their billion dances?
-pub fn part2(moves: &[Move]) -> String {
-    let dance_times = 1_000_000_000 % find_cycle(moves);
+pub fn part2_with_programs(moves: &[Move], count: usize, dances: usize) -> String {
+    let dance_times = dances % find_cycle(moves, count);
 
-    let mut programs = initial_programs();
+    let mut programs = initial_programs(count);
 
     for _ in 0..dance_times {
         programs = dance(programs, moves);
@@ -208,10 +213,19 @@ pub fn part2(moves: &[Move]) -> String {
     programs.into_iter().collect()
 }
 
-pub fn parse_input(input: &str) -> Vec<Move> {
-    Move::list_from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing moves")
+pub fn part2(moves: &[Move]) -> String {
+    part2_with_programs(moves, 16, 1_000_000_000)
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Move>, ParseError> {
+    parse::complete(input, Move::list_from_bytes(input.as_bytes()), "a dance move")
 }
 
-test_day!("16", "nlciboghjmfdapek", "nlciboghmkedpfja");
+solution!(Vec<Move>);
+
+test_examples!(
+    ("s1,x3/4,pe/b\n", |input| part1_with_programs(input, 5), "baedc"),
+    ("s1,x3/4,pe/b\n", |input| part2_with_programs(input, 5, 2), "ceadb"),
+);
+
+test_day!(2017, "16", "nlciboghjmfdapek", "nlciboghmkedpfja");
@@ -28,6 +28,9 @@
 //!
 //!   [spinlock]: https://en.wikipedia.org/wiki/Spinlock
 
+use ::parse;
+use ::ParseError;
+
 /// For example, if the spinlock were to step `3` times per insert, the
 /// circular buffer would begin to evolve like this (using parentheses to
 /// mark the current position after each iteration of the algorithm):
@@ -110,10 +113,14 @@ pub fn part2(input: &usize) -> usize {
     last_i
 }
 
-pub fn parse_input(input: &str) -> usize {
-    input[..input.len() - 1]
-        .parse::<usize>()
-        .expect("Unexpected non-integer")
+pub fn parse_input(input: &str) -> Result<usize, ParseError> {
+    parse::token(input, input.trim_end(), "an integer")
 }
 
-test_day!("17", 1561, 33454823);
+solution!(usize);
+
+test_examples!(
+    ("3\n", 1, 638usize),
+);
+
+test_day!(2017, "17", 1561, 33454823);
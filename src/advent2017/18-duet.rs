@@ -11,6 +11,8 @@
 use std::collections::{ HashMap, VecDeque };
 use nom::anychar;
 use ::parse::signed_number;
+use ::parse;
+use ::ParseError;
 
 /// Many of the instructions can take either a register (a single letter) or
 /// a number. The value of a register is the integer it contains; the value
@@ -456,10 +458,15 @@ pub fn part2(instructions: &[Instruction]) -> usize {
 }
 
 
-pub fn parse_input(input: &str) -> Vec<Instruction> {
-    Instruction::list_from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing instructions")
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::complete(input, Instruction::list_from_bytes(input.as_bytes()), "an instruction")
 }
 
-test_day!("18", 3423, 7493);
+solution!(Vec<Instruction>);
+
+test_examples!(
+    ("set a 1\nadd a 2\nmul a a\nmod a 5\nsnd a\nset a 0\nrcv a\njgz a -1\nset a 1\njgz a -2\n", 1, 4isize),
+    ("snd 1\nsnd 2\nsnd p\nrcv a\nrcv b\nrcv c\nrcv d\n", 2, 3usize),
+);
+
+test_day!(2017, "18", 3423, 7493);
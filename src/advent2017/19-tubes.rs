@@ -6,23 +6,24 @@
 
 use ::Direction;
 use ::Direction::*;
+use ::grid::Grid;
+use ::{ ParseError, Point2 };
 
 /// Its starting point is just off the top of the diagram. Lines (drawn with
 /// `|`, `-`, and `+`) show the path it needs to take, starting by going
 /// down onto the only line connected to the top of the diagram. It needs to
 /// follow this path until it reaches the end (located somewhere within the
 /// diagram) and stop there.
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
-enum Cell {
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub enum Cell {
     Road,
+    #[default]
     Empty,
     Letter(char),
 }
 
 use self::Cell::*;
 
-type Grid = Vec<Vec<Cell>>;
-
 impl From<char> for Cell {
     fn from(c: char) -> Self {
         match c {
@@ -60,7 +61,7 @@ impl From<char> for Cell {
 /// `ABCDEF`.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day19::solve;
+/// # use advent_solutions::advent2017::day19::{ parse_input, solve };
 /// # let input = [
 /// #     "     |          \n",
 /// #     "     |  +--+    \n",
@@ -72,12 +73,12 @@ impl From<char> for Cell {
 /// # .iter()
 /// # .map(|x| *x)
 /// # .collect::<String>();
-/// let (collected, _) = solve(&input);
+/// let (collected, _) = solve(&parse_input(&input).unwrap());
 /// assert_eq!(collected, "ABCDEF");
 /// ```
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
 struct Packet {
-    position: (usize, usize),
+    position: Point2<usize>,
     direction: Direction,
     collected: Vec<char>,
 }
@@ -85,45 +86,35 @@ struct Packet {
 impl Packet {
     fn new(x: usize) -> Packet {
         Packet {
-            position: (x, 0),
+            position: Point2::new(x, 0),
             direction: Down,
             collected: Vec::new(),
         }
     }
 
-    fn step(&mut self, grid: &Grid) -> bool {
-        if let Letter(c) = grid[self.position.1][self.position.0] {
+    fn step(&mut self, grid: &Grid<Cell>) -> bool {
+        if let Letter(c) = grid[self.position] {
             self.collected.push(c);
         }
 
         let n = self.neighbors(grid);
+        let turns = [self.direction, self.direction.cw(), self.direction.ccw()];
 
-        if n.iter().any(|&x| x == self.direction) {
-            self.position += self.direction;
-        } else if n.iter().any(|&x| x == self.direction.cw()) {
-            self.direction = self.direction.cw();
-            self.position += self.direction;
-        } else if n.iter().any(|&x| x == self.direction.ccw()) {
-            self.direction = self.direction.ccw();
-            self.position += self.direction;
-        } else {
-            return false;
-        }
+        match turns.iter().find(|direction| n.contains(direction)) {
+            Some(&direction) => {
+                self.direction = direction;
+                // Neighbors are all inside the diagram.
+                self.position = self.position.step(direction).unwrap();
 
-        true
+                true
+            },
+            None => false,
+        }
     }
 
-    fn neighbors(&self, grid: &Grid) -> Vec<Direction> {
-        let w = grid[0].len();
-        let h = grid.len();
-
-        [Up, Down, Left, Right].iter()
-            .map(|&dir| (dir, self.position + dir))
-            .filter(|&(_, (x, y))|
-                x > 0 && y > 0
-                && x < w && y < h
-                && grid[y as usize][x as usize] != Empty
-            )
+    fn neighbors(&self, grid: &Grid<Cell>) -> Vec<Direction> {
+        grid.neighbors4(self.position.into())
+            .filter(|&(_, position)| grid[position] != Empty)
             .map(|(dir, _)| dir)
             .collect()
     }
@@ -164,7 +155,7 @@ impl Packet {
 /// This would result in a total of `38` steps.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day19::solve;
+/// # use advent_solutions::advent2017::day19::{ parse_input, solve };
 /// # let input = [
 /// #     "     |          \n",
 /// #     "     |  +--+    \n",
@@ -176,24 +167,19 @@ impl Packet {
 /// # .iter()
 /// # .map(|x| *x)
 /// # .collect::<String>();
-/// let (_, steps) = solve(&input);
+/// let (_, steps) = solve(&parse_input(&input).unwrap());
 /// assert_eq!(steps, 38);
 /// ```
 ///
 /// *How many steps* does the packet need to go?
-pub fn solve(input: &str) -> (String, usize) {
-    let grid: Grid = input
-        .lines()
-        .map(|line| line.chars().map(Into::<Cell>::into).collect())
-        .collect();
-
-    let x = grid[0].iter().position(|&c| c == Road)
+pub fn solve(grid: &Grid<Cell>) -> (String, usize) {
+    let x = grid.row(0).iter().position(|&c| c == Road)
         .expect("Could not find starting point");
 
     let mut packet = Packet::new(x);
     let mut steps = 1;
 
-    while packet.step(&grid) {
+    while packet.step(grid) {
         steps += 1;
     }
 
@@ -202,8 +188,26 @@ pub fn solve(input: &str) -> (String, usize) {
     (collected, steps)
 }
 
-pub fn parse_input(input: &str) -> &str {
-    input
+pub fn parse_input(input: &str) -> Result<Grid<Cell>, ParseError> {
+    // Rows may have lost their trailing spaces.
+    Grid::parse_padded(input, "a diagram cell")
 }
 
-test_day_both!("19", "LXWCKGRAOY", 17302);
+solution_both!(Grid<Cell>);
+
+#[cfg(test)]
+const EXAMPLE: &str = concat!(
+    "     |          \n",
+    "     |  +--+    \n",
+    "     A  |  C    \n",
+    " F---|----E|--+ \n",
+    "     |  |  |  D \n",
+    "     +B-+  +--+ \n",
+);
+
+test_examples!(
+    (EXAMPLE, 1, "ABCDEF"),
+    (EXAMPLE, 2, 38usize),
+);
+
+test_day_both!(2017, "19", "LXWCKGRAOY", 17302);
@@ -10,15 +10,17 @@
 
 use std::cmp::min;
 use ::parse::signed_number;
+use ::parse;
+use ::{ ParseError, Point3 };
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
 pub struct Particle {
-    p: (isize, isize, isize),
-    v: (isize, isize, isize),
-    a: (isize, isize, isize),
+    p: Point3<isize>,
+    v: Point3<isize>,
+    a: Point3<isize>,
 }
 
-named!{ parse_vector (&[u8]) -> (isize, isize, isize),
+named!{ parse_vector (&[u8]) -> Point3<isize>,
     do_parse!(
         char!('<') >>
         x: ws!(signed_number) >>
@@ -28,7 +30,7 @@ named!{ parse_vector (&[u8]) -> (isize, isize, isize),
         z: ws!(signed_number) >>
         char!('>') >>
 
-        ((x as isize, y as isize, z as isize))
+        (Point3::new(x as isize, y as isize, z as isize))
     )
 }
 
@@ -93,7 +95,7 @@ named!{ parse_particles (&[u8]) -> Vec<Particle>,
 /// # use advent_solutions::advent2017::day20::{ parse_input, part1 };
 /// # let input = parse_input("p=< 3,0,0>, v=< 2,0,0>, a=<-1,0,0>
 /// # p=< 4,0,0>, v=< 0,0,0>, a=<-2,0,0>
-/// # ");
+/// # ").unwrap();
 /// assert_eq!(part1(&input), 0);
 /// ```
 ///
@@ -115,9 +117,9 @@ pub fn part1(particles: &Vec<Particle>) -> usize {
             (
                 n,
                 particle,
-                a.0 * a.0 + a.1 * a.1 + a.2 * a.2,
-                v.0 * v.0 + v.1 * v.1 + v.2 * v.2,
-                p.0 * p.0 + p.1 * p.1 + p.2 * p.2,
+                a.norm_squared(),
+                v.norm_squared(),
+                p.norm_squared(),
             )
 
         })
@@ -255,7 +257,7 @@ fn combine_roots(x: Roots, y: Roots) -> Roots {
 /// # p=<-4,0,0>, v=< 2,0,0>, a=< 0,0,0>
 /// # p=<-2,0,0>, v=< 1,0,0>, a=< 0,0,0>
 /// # p=< 3,0,0>, v=<-1,0,0>, a=< 0,0,0>
-/// # ");
+/// # ").unwrap();
 /// assert_eq!(part2(&input), 1);
 /// ```
 ///
@@ -271,23 +273,11 @@ pub fn part2(particles: &Vec<Particle>) -> usize {
             let (n0, p0) = particles[0];
             let (n1, p1) = particles[1];
 
-            let rx = Roots::solve_quadratic(
-                p0.a.0 - p1.a.0,
-                2 * (p0.v.0 - p1.v.0) + (p0.a.0 - p1.a.0),
-                2 * (p0.p.0 - p1.p.0),
-            );
+            let (dp, dv, da) = (p0.p - p1.p, p0.v - p1.v, p0.a - p1.a);
 
-            let ry = Roots::solve_quadratic(
-                p0.a.1 - p1.a.1,
-                2 * (p0.v.1 - p1.v.1) + (p0.a.1 - p1.a.1),
-                2 * (p0.p.1 - p1.p.1),
-            );
-
-            let rz = Roots::solve_quadratic(
-                p0.a.2 - p1.a.2,
-                2 * (p0.v.2 - p1.v.2) + (p0.a.2 - p1.a.2),
-                2 * (p0.p.2 - p1.p.2),
-            );
+            let rx = Roots::solve_quadratic(da.x, 2 * dv.x + da.x, 2 * dp.x);
+            let ry = Roots::solve_quadratic(da.y, 2 * dv.y + da.y, 2 * dp.y);
+            let rz = Roots::solve_quadratic(da.z, 2 * dv.z + da.z, 2 * dp.z);
 
             match combine_roots(rx, combine_roots(ry, rz)) {
                 Roots::None => None,
@@ -322,10 +312,16 @@ pub fn part2(particles: &Vec<Particle>) -> usize {
 /// and so on). For each particle, it provides the `X`, `Y`, and `Z`
 /// coordinates for the particle's position (`p`), velocity (`v`), and
 /// acceleration (`a`), each in the format `<X,Y,Z>`.
-pub fn parse_input(input: &str) -> Vec<Particle> {
-    parse_particles(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing particles")
+pub fn parse_input(input: &str) -> Result<Vec<Particle>, ParseError> {
+    parse::complete(input, parse_particles(input.as_bytes()), "a particle")
 }
 
-test_day!("20", 300, 502);
+solution!(Vec<Particle>);
+
+test_examples!(
+    ("p=< 3,0,0>, v=< 2,0,0>, a=<-1,0,0>\np=< 4,0,0>, v=< 0,0,0>, a=<-2,0,0>\n", 1, 0usize),
+    ("p=<-6,0,0>, v=< 3,0,0>, a=< 0,0,0>\np=<-4,0,0>, v=< 2,0,0>, a=< 0,0,0>\n\
+      p=<-2,0,0>, v=< 1,0,0>, a=< 0,0,0>\np=< 3,0,0>, v=<-1,0,0>, a=< 0,0,0>\n", 2, 1usize),
+);
+
+test_day!(2017, "20", 300, 502);
@@ -5,149 +5,76 @@
 //! TV.">repeatedly enhancing</span> the detail of an image through a set of
 //! rules.
 
-use std::fmt;
+use std::convert::TryFrom;
 use std::collections::HashMap;
+use ::grid::Grid;
+use ::parse;
+use ::ParseError;
 
 /// The image consists of a two-dimensional square grid of pixels that are
 /// either on (`#`) or off (`.`).
-#[derive(Clone, PartialEq, Eq, Debug, Hash)]
-pub struct Grid(Vec<Vec<bool>>);
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Pixel { Off, On }
 
-impl Grid {
-    fn region(&self, x: usize, y: usize, w: usize, h: usize) -> Grid {
-        Grid(
-            (0..h)
-            .map(|yy| (0..w)
-                .map(|xx| self.0[y + yy][x + xx])
-                .collect()
-            )
-            .collect()
-        )
-    }
-
-    fn split(&self) -> Vec<Vec<Grid>> {
-        let size = self.0.len();
+use self::Pixel::*;
 
-        if size % 2 == 0 {
-            let tiles = size / 2;
+impl TryFrom<char> for Pixel {
+    type Error = &'static str;
 
-            (0..tiles)
-            .map(|y| (0..tiles)
-                .map(|x| self.region(x * 2, y * 2, 2, 2))
-                .collect()
-            )
-            .collect()
-        } else {
-            let tiles = size / 3;
-
-            (0..tiles)
-            .map(|y| (0..tiles)
-                .map(|x| self.region(x * 3, y * 3, 3, 3))
-                .collect()
-            )
-            .collect()
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '.' => Ok(Off),
+            '#' => Ok(On),
+            _ => Err("Invalid pixel character"),
         }
     }
+}
 
-    /// When searching for a rule to use, rotate and flip the pattern as
-    /// necessary. For example, all of the following patterns match the same
-    /// rule:
-    ///
-    /// ```text
-    /// .#.   .#.   #..   ###
-    /// ..#   #..   #.#   ..#
-    /// ###   ###   ##.   .#.
-    /// ```
-    fn flips_and_rotations(&self) -> Vec<Grid> {
-        let mut result = vec![self.clone()];
-
-        let size = self.0.len();
-
-        let mut swap = self.clone();
-        let mut flip_y = self.clone();
-        let mut flip_y_swap = self.clone();
-        let mut flip_x = self.clone();
-        let mut flip_x_swap = self.clone();
-        let mut flip_both = self.clone();
-        let mut flip_both_swap = self.clone();
-
-        for y in 0..size {
-            for x in 0..size {
-                swap.0[y][x] = self.0[x][y];
-                flip_y.0[y][x] = self.0[size - y - 1][x];
-                flip_y_swap.0[y][x] = self.0[x][size - y - 1];
-                flip_x.0[y][x] = self.0[y][size - x - 1];
-                flip_x_swap.0[y][x] = self.0[size - x - 1][y];
-                flip_both.0[y][x] = self.0[size - y - 1][size - x - 1];
-                flip_both_swap.0[y][x] = self.0[size - x - 1][size - y - 1];
-            }
+impl From<Pixel> for char {
+    fn from(pixel: Pixel) -> Self {
+        match pixel {
+            Off => '.',
+            On => '#',
         }
-
-        result.push(swap);
-        result.push(flip_y);
-        result.push(flip_y_swap);
-        result.push(flip_x);
-        result.push(flip_x_swap);
-        result.push(flip_both);
-        result.push(flip_both_swap);
-
-        result
     }
+}
 
-    fn merge(tiles: Vec<Vec<Grid>>) -> Grid {
-        let num_tiles = tiles.len();
-        let size = tiles[0][0].0.len();
-
-        let mut result = vec![];
-
-        for y_tile in 0..num_tiles {
-            for y in 0..size {
-                let mut row = vec![];
-
-                for x_tile in 0..num_tiles {
-                    for x in 0..size {
-                        row.push(tiles[y_tile][x_tile].0[y][x]);
-                    }
-                }
+/// Splits a square image into 2x2 tiles if its size is even, or else into
+/// 3x3 tiles.
+fn split(image: &Grid<Pixel>) -> Vec<Vec<Grid<Pixel>>> {
+    let size = image.width();
+    let tile = if size.is_multiple_of(2) { 2 } else { 3 };
+    let tiles = size / tile;
 
-                result.push(row);
-            }
-        }
+    (0..tiles)
+    .map(|y| (0..tiles)
+        .map(|x| image.region((x * tile, y * tile), (tile, tile)))
+        .collect()
+    )
+    .collect()
+}
 
-        Grid(result)
-    }
+fn merge(tiles: &[Vec<Grid<Pixel>>]) -> Grid<Pixel> {
+    let tile = tiles[0][0].width();
+    let size = tiles.len() * tile;
 
-    named!{ cell_from_bytes (&[u8]) -> Vec<bool>,
-        many1!(
-            map!(
-                alt!(char!('#') | char!('.')),
-                |x| x == '#'
-            )
-        )
-    }
+    Grid::from_fn(size, size, |(x, y)| tiles[y / tile][x / tile][(x % tile, y % tile)])
+}
 
-    named!{ pub from_bytes (&[u8]) -> Grid,
-        map!(
-            separated_list!(char!('/'), Grid::cell_from_bytes),
-            |x| Grid(x)
+named!{ parse_pixels (&[u8]) -> Vec<Pixel>,
+    many1!(
+        map_res!(
+            alt!(char!('#') | char!('.')),
+            Pixel::try_from
         )
-    }
+    )
 }
 
-impl fmt::Display for Grid {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let size = self.0.len();
-
-        for y in 0..size {
-            for x in 0..size {
-                write!(f, "{}", if self.0[y][x] { '#' } else { '.' })?
-            }
-
-            write!(f, "\n")?
-        }
-
-        Ok(())
-    }
+named!{ parse_image (&[u8]) -> Grid<Pixel>,
+    map_opt!(
+        separated_list!(char!('/'), parse_pixels),
+        Grid::from_rows
+    )
 }
 
 /// The artist's book of enhancement rules is nearby (your puzzle input);
@@ -171,13 +98,16 @@ impl fmt::Display for Grid {
 ///                         #..#
 ///                         .##.
 /// ```
-type Rule = (Grid, Grid);
+type Rule = (Grid<Pixel>, Grid<Pixel>);
+
+/// The output of each input pattern, flipped and rotated in every way.
+pub type Rules = HashMap<Grid<Pixel>, Grid<Pixel>>;
 
 named!{ parse_rule (&[u8]) -> Rule,
     do_parse!(
-        l: call!(Grid::from_bytes) >>
+        l: parse_image >>
         tag!(" => ") >>
-        r: call!(Grid::from_bytes) >>
+        r: parse_image >>
 
         (l, r)
     )
@@ -273,25 +203,19 @@ named!{ parse_rules (&[u8]) -> Vec<Rule>,
 /// Thus, after `2` iterations, the grid contains `12` pixels that are *on*.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day21::{ parse_input, Grid, solve };
+/// # use advent_solutions::advent2017::day21::{ parse_input, solve };
 /// let input = parse_input("\
 /// ../.# => ##./#../...
 /// .#./..#/### => #..#/..../..../#..#
-/// ");
+/// ").unwrap();
 ///
 /// assert_eq!(solve(&input, 2), 12);
 /// ```
-pub fn solve(input: &HashMap<Grid, Grid>, iterations: usize) -> usize {
-    let mut grid = Grid(
-        vec![
-            vec![false, true, false],
-            vec![false, false, true],
-            vec![true, true, true],
-        ]
-    );
+pub fn solve(input: &Rules, iterations: usize) -> usize {
+    let mut grid = ".#.\n..#\n###\n".parse::<Grid<Pixel>>().unwrap();
 
     for _ in 0..iterations {
-        let mut split = grid.split();
+        let mut split = split(&grid);
 
         for y in &mut split {
             for x in y {
@@ -301,34 +225,46 @@ pub fn solve(input: &HashMap<Grid, Grid>, iterations: usize) -> usize {
             }
         }
 
-        grid = Grid::merge(split);
+        grid = merge(&split);
     }
 
-    grid.0.into_iter()
-        .map(|row| row.into_iter().filter(|&x| x).count())
-        .sum::<usize>()
+    grid.iter().filter(|&&pixel| pixel == On).count()
 }
 
 /// *How many pixels stay on* after `5` iterations?
-pub fn part1(input: &HashMap<Grid, Grid>) -> usize {
+pub fn part1(input: &Rules) -> usize {
     solve(input, 5)
 }
 
 /// *How many pixels stay on* after `18` iterations?
-pub fn part2(input: &HashMap<Grid, Grid>) -> usize {
+pub fn part2(input: &Rules) -> usize {
     solve(input, 18)
 }
 
-pub fn parse_input(input: &str) -> HashMap<Grid, Grid> {
-    parse_rules(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing rules")
-        .into_iter()
+/// When searching for a rule to use, rotate and flip the pattern as
+/// necessary. For example, all of the following patterns match the same
+/// rule:
+///
+/// ```text
+/// .#.   .#.   #..   ###
+/// ..#   #..   #.#   ..#
+/// ###   ###   ##.   .#.
+/// ```
+pub fn parse_input(input: &str) -> Result<Rules, ParseError> {
+    let rules = parse::complete(input, parse_rules(input.as_bytes()), "a rule")?;
+
+    Ok(rules.into_iter()
         .flat_map(|(k, v)| k.flips_and_rotations().into_iter()
             .map(|rot| (rot, v.clone()))
             .collect::<Vec<_>>()
         )
-        .collect()
+        .collect())
 }
 
-test_day!("21", 150, 2606275);
+solution!(Rules);
+
+test_examples!(
+    ("../.# => ##./#../...\n.#./..#/### => #..#/..../..../#..#\n", |input| solve(input, 2), 12),
+);
+
+test_day!(2017, "21", 150, 2606275);
@@ -6,15 +6,16 @@
 //! infinite</span> two-dimensional grid of compute nodes. Each node is either
 //! *clean* or *infected* by the virus.
 
-use std::{ fmt, ops };
+use std::fmt;
 use std::convert::TryFrom;
-use std::collections::VecDeque;
 
 use ::Direction;
 use ::Direction::*;
+use ::grid::{ Grid, InfiniteGrid };
+use ::{ ParseError, Point2 };
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
-pub enum Node { Clean, Weakened, Infected, Flagged }
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash, Default)]
+pub enum Node { #[default] Clean, Weakened, Infected, Flagged }
 
 use self::Node::*;
 
@@ -65,127 +66,13 @@ impl fmt::Display for Node {
 /// are shown as `#`. This map only shows the center of the grid; there are
 /// many more nodes beyond those shown, but none of them are currently
 /// infected.
-#[derive(Clone, PartialEq, Eq, Debug, Hash)]
-pub struct Memory {
-    memory: VecDeque<VecDeque<Node>>,
-    origin: (usize, usize),
-}
-
-impl Memory {
-    fn parse(input: &str) -> Memory {
-        let memory = input.lines()
-            .map(|line| line.chars()
-                .map(|c| Node::try_from(c).unwrap())
-                .collect::<VecDeque<_>>()
-            )
-            .collect::<VecDeque<_>>();
-
-        let height = memory.len();
-        let width = memory[0].len();
-
-        Memory {
-            memory,
-            origin: (height / 2, width / 2),
-        }
-    }
+pub type Memory = InfiniteGrid<Node>;
 
-    fn width(&self) -> usize {
-        self.memory[0].len()
-    }
+/// The memory, with the center of the map at `(0, 0)`.
+fn memory(map: &Grid<Node>) -> Memory {
+    let (x, y) = (map.width() / 2, map.height() / 2);
 
-    fn height(&self) -> usize {
-        self.memory.len()
-    }
-}
-
-impl ops::Index<(isize, isize)> for Memory {
-    type Output = Node;
-    fn index(&self, (x, y): (isize, isize)) -> &Self::Output {
-        let ny = self.origin.0 as isize + y;
-        let nx = self.origin.1 as isize + x;
-
-        if ny >= self.height() as isize
-            || ny < 0
-            || nx >= self.width() as isize
-            || nx < 0 {
-            return &Clean;
-        }
-
-        &self.memory[ny as usize][nx as usize]
-    }
-}
-
-impl ops::IndexMut<(isize, isize)> for Memory {
-    fn index_mut(&mut self, (x, y): (isize, isize)) -> &mut Self::Output {
-        let ny = self.origin.0 as isize + y;
-        let nx = self.origin.1 as isize + x;
-
-        if ny >= self.height() as isize {
-            let grow = ny - self.height() as isize + 1;
-
-            for _ in 0..grow {
-                let mut row = VecDeque::new();
-                row.resize(self.width(), Clean);
-                self.memory.push_back(row);
-            }
-        } else if ny < 0 {
-            let grow = ny.abs();
-
-            for _ in 0..grow {
-                let mut row = VecDeque::new();
-                row.resize(self.width(), Clean);
-                self.memory.push_front(row);
-            }
-
-            self.origin.0 += grow as usize;
-        }
-
-        let ny = self.origin.0 as isize + y;
-
-        if nx >= self.width() as isize {
-            let grow = nx - self.width() as isize + 1;
-
-            self.memory.iter_mut().for_each(|row| for _ in 0..grow {
-                row.push_back(Clean)
-            });
-        } else if nx < 0 {
-            let grow = nx.abs();
-
-            self.memory.iter_mut().for_each(|row| for _ in 0..grow {
-                row.push_front(Clean)
-            });
-
-            self.origin.1 += grow as usize;
-        }
-
-        let nx = self.origin.1 as isize + x;
-
-        &mut self.memory[ny as usize][nx as usize]
-    }
-}
-
-impl fmt::Display for Memory {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.memory.iter()
-            .map(|line| {
-                line.iter().map(|x| write!(f, "{}", x))
-                    .skip_while(|x| x.is_ok())
-                    .next()
-                    .unwrap_or(Ok(()))
-                    .and_then(|_| write!(f, "\n"))
-            })
-            .skip_while(|x| x.is_ok())
-            .next()
-            .unwrap_or(Ok(()))
-            .and_then(|_| (0..self.width())
-                .map(|i| write!(f, "{}", if i == self.origin.1 { "|" } else { " " }))
-                .skip_while(|x| x.is_ok())
-                .next()
-                .unwrap_or(Ok(()))
-                .and_then(|_| write!(f, "\n"))
-            )
-            .and_then(|_| write!(f, "\n"))
-    }
+    Memory::from_grid(map, (-(x as isize), -(y as isize)))
 }
 
 /// To [prevent overloading] the nodes (which would render them useless to
@@ -200,7 +87,7 @@ impl fmt::Display for Memory {
 ///   [prevent overloading]: https://en.wikipedia.org/wiki/Morris_worm#The_mistake
 #[derive(Clone, PartialEq, Eq, Debug, Hash)]
 pub struct Carrier {
-    position: (isize, isize),
+    position: Point2<isize>,
     facing: Direction,
     infected: usize,
 }
@@ -208,7 +95,7 @@ pub struct Carrier {
 impl Carrier {
     fn new() -> Carrier {
         Carrier {
-            position: (0, 0),
+            position: Point2::new(0, 0),
             facing: Up,
             infected: 0,
         }
@@ -397,31 +284,31 @@ impl Carrier {
 /// of those nodes have since been cleaned).
 ///
 /// ```
-/// # use advent_solutions::advent2017::day22::part1_with_bursts;
-/// # let input = "..#
+/// # use advent_solutions::advent2017::day22::{ parse_input, part1_with_bursts };
+/// # let input = parse_input("..#
 /// # #..
 /// # ...
-/// # ";
-/// assert_eq!(part1_with_bursts(input, 70), 41);
+/// # ").unwrap();
+/// assert_eq!(part1_with_bursts(&input, 70), 41);
 /// ```
 ///
 /// After a total of `10000` bursts of activity, `5587` bursts will have
 /// caused an infection.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day22::part1_with_bursts;
-/// # let input = "..#
+/// # use advent_solutions::advent2017::day22::{ parse_input, part1_with_bursts };
+/// # let input = parse_input("..#
 /// # #..
 /// # ...
-/// # ";
-/// assert_eq!(part1_with_bursts(input, 10000), 5587);
+/// # ").unwrap();
+/// assert_eq!(part1_with_bursts(&input, 10000), 5587);
 /// ```
 ///
 /// Given your actual map, after `10000` bursts of activity, *how many
 /// bursts cause a node to become infected*? (Do not count nodes that begin
 /// infected.)
-pub fn part1_with_bursts(input: &str, bursts: usize) -> usize {
-    let mut memory = Memory::parse(input);
+pub fn part1_with_bursts(map: &Grid<Node>, bursts: usize) -> usize {
+    let mut memory = memory(map);
     let mut carrier = Carrier::new();
 
     for _ in 0..bursts {
@@ -431,8 +318,8 @@ pub fn part1_with_bursts(input: &str, bursts: usize) -> usize {
     carrier.infected
 }
 
-pub fn part1(input: &str) -> usize {
-    part1_with_bursts(input, 10000)
+pub fn part1(map: &Grid<Node>) -> usize {
+    part1_with_bursts(map, 10000)
 }
 
 /// Start with the same map (still using `.` for *clean* and `#` for
@@ -530,21 +417,21 @@ pub fn part1(input: &str) -> usize {
 /// first `10000000` bursts, `2511944` will result in *infection*.
 ///
 /// ```
-/// # use advent_solutions::advent2017::day22::part2_with_bursts;
-/// let input = "..#
+/// # use advent_solutions::advent2017::day22::{ parse_input, part2_with_bursts };
+/// let input = parse_input("..#
 /// #..
 /// ...
-/// ";
+/// ").unwrap();
 ///
-/// assert_eq!(part2_with_bursts(input, 100), 26);
-/// assert_eq!(part2_with_bursts(input, 10000000), 2511944);
+/// assert_eq!(part2_with_bursts(&input, 100), 26);
+/// assert_eq!(part2_with_bursts(&input, 10000000), 2511944);
 /// ```
 ///
 /// Given your actual map, after `10000000` bursts of activity, *how many
 /// bursts cause a node to become infected*? (Do not count nodes that begin
 /// infected.)
-pub fn part2_with_bursts(input: &str, bursts: usize) -> usize {
-    let mut memory = Memory::parse(&input);
+pub fn part2_with_bursts(map: &Grid<Node>, bursts: usize) -> usize {
+    let mut memory = memory(map);
     let mut carrier = Carrier::new();
 
     for _ in 0..bursts {
@@ -554,12 +441,22 @@ pub fn part2_with_bursts(input: &str, bursts: usize) -> usize {
     carrier.infected
 }
 
-pub fn part2(input: &str) -> usize {
-    part2_with_bursts(input, 10000000)
+pub fn part2(map: &Grid<Node>) -> usize {
+    part2_with_bursts(map, 10000000)
 }
 
-pub fn parse_input(input: &str) -> &str {
-    input
+pub fn parse_input(input: &str) -> Result<Grid<Node>, ParseError> {
+    Grid::parse(input, "a node")
 }
 
-test_day!("22", 5259, 2511722);
+solution!(Grid<Node>);
+
+test_examples!(
+    ("..#\n#..\n...\n", |input| part1_with_bursts(input, 7), 5),
+    ("..#\n#..\n...\n", |input| part1_with_bursts(input, 70), 41),
+    ("..#\n#..\n...\n", 1, 5587usize),
+    ("..#\n#..\n...\n", |input| part2_with_bursts(input, 100), 26),
+    ("..#\n#..\n...\n", 2, 2511944usize),
+);
+
+test_day!(2017, "22", 5259, 2511722);
@@ -11,6 +11,8 @@
 use std::collections::HashMap;
 use nom::anychar;
 use ::parse::signed_number;
+use ::parse;
+use ::ParseError;
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 pub enum Value {
@@ -256,10 +258,17 @@ pub fn part2(instructions: &[Instruction]) -> usize {
     }
 }
 
-pub fn parse_input(input: &str) -> Vec<Instruction> {
-    Instruction::list_from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing instructions")
+pub fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    parse::complete(input, Instruction::list_from_bytes(input.as_bytes()), "an instruction")
 }
 
-test_day!("23", 6241, 909);
+solution!(Vec<Instruction>);
+
+// The puzzle has no examples, and part 2 only makes sense for the actual
+// program (see `part2`), so this just checks that skipped `mul`s are not
+// counted.
+test_examples!(
+    ("set a 2\nmul a a\njnz a 2\nmul a 3\nmul a 5\n", 1, 2isize),
+);
+
+test_day!(2017, "23", 6241, 909);
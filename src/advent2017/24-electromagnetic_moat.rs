@@ -10,6 +10,8 @@
 
 use itertools::Itertools;
 use ::parse::unsigned_number;
+use ::parse;
+use ::ParseError;
 
 /// Each component has two *ports*, one on each end. The ports come in all
 /// different types, and only matching types can be connected. You take an
@@ -144,7 +146,7 @@ fn dfs(
 /// 0/1
 /// 10/1
 /// 9/10
-/// ");
+/// ").unwrap();
 ///
 /// assert_eq!(part1(&input), 31);
 /// ```
@@ -180,7 +182,7 @@ pub fn part1(graph: &Vec<Component>) -> usize {
 /// 0/1
 /// 10/1
 /// 9/10
-/// ");
+/// ").unwrap();
 ///
 /// assert_eq!(part2(&input), 19);
 /// ```
@@ -195,10 +197,27 @@ pub fn part2(graph: &Vec<Component>) -> usize {
     )
 }
 
-pub fn parse_input(input: &str) -> Vec<Component> {
-    Component::list_from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing components")
+pub fn parse_input(input: &str) -> Result<Vec<Component>, ParseError> {
+    parse::complete(input, Component::list_from_bytes(input.as_bytes()), "a component")
 }
 
-test_day!("24", 1868, 1841);
+solution!(Vec<Component>);
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+0/2
+2/2
+2/3
+3/4
+3/5
+0/1
+10/1
+9/10
+";
+
+test_examples!(
+    (EXAMPLE, 1, 31usize),
+    (EXAMPLE, 2, 19usize),
+);
+
+test_day!(2017, "24", 1868, 1841);
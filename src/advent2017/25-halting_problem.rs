@@ -34,6 +34,8 @@
 use std::collections::HashMap;
 use nom::anychar;
 use ::parse::unsigned_number;
+use ::parse;
+use ::ParseError;
 
 named!{ parse_bool (&[u8]) -> bool,
     map!(
@@ -213,7 +215,7 @@ Perform a diagnostic checksum after ") >>
 /// #     - Write the value 1.
 /// #     - Move one slot to the right.
 /// #     - Continue with state A.
-/// # ");
+/// # ").unwrap();
 /// #
 /// assert_eq!(part1(&input), 3);
 /// ```
@@ -292,14 +294,42 @@ pub fn part1(input: &Blueprint) -> usize {
 /// You must deposit 49 stars to increase your priority to the required level.
 /// ```
 /// The *garbage collector* winks at you, then continues sweeping.
-pub fn part2(_: &Blueprint) -> &str {
-    ""
-}
+pub fn part2(_: &Blueprint) {}
 
-pub fn parse_input(input: &str) -> Blueprint {
-    Blueprint::from_bytes(input.as_bytes())
-        .to_full_result()
-        .expect("Error parsing blueprint")
+pub fn parse_input(input: &str) -> Result<Blueprint, ParseError> {
+    parse::complete(input, Blueprint::from_bytes(input.as_bytes()), "a blueprint")
 }
 
-test_day!("25", 2846, "");
+solution!(Blueprint);
+
+#[cfg(test)]
+const EXAMPLE: &str = "\
+Begin in state A.
+Perform a diagnostic checksum after 6 steps.
+
+In state A:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state B.
+  If the current value is 1:
+    - Write the value 0.
+    - Move one slot to the left.
+    - Continue with state B.
+
+In state B:
+  If the current value is 0:
+    - Write the value 1.
+    - Move one slot to the left.
+    - Continue with state A.
+  If the current value is 1:
+    - Write the value 1.
+    - Move one slot to the right.
+    - Continue with state A.
+";
+
+test_examples!(
+    (EXAMPLE, 1, 3usize),
+);
+
+test_day!(2017, "25", 2846, ());
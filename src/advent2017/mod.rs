@@ -29,3 +29,38 @@ pub mod knot_hash;
 #[path="23-coprocessor_conflagration.rs"] pub mod day23;
 #[path="24-electromagnetic_moat.rs"] pub mod day24;
 #[path="25-halting_problem.rs"] pub mod day25;
+
+use ::Day;
+
+pub const YEAR: usize = 2017;
+
+/// Every solved day, to be included in the [`registry`](../fn.registry.html).
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Solver>(YEAR, 1),
+        Day::new::<day02::Solver>(YEAR, 2),
+        Day::new::<day03::Solver>(YEAR, 3),
+        Day::new::<day04::Solver>(YEAR, 4),
+        Day::new::<day05::Solver>(YEAR, 5),
+        Day::new::<day06::Solver>(YEAR, 6),
+        Day::new::<day07::Solver>(YEAR, 7),
+        Day::new::<day08::Solver>(YEAR, 8),
+        Day::new::<day09::Solver>(YEAR, 9),
+        Day::new::<day10::Solver>(YEAR, 10),
+        Day::new::<day11::Solver>(YEAR, 11),
+        Day::new::<day12::Solver>(YEAR, 12),
+        Day::new::<day13::Solver>(YEAR, 13),
+        Day::new::<day14::Solver>(YEAR, 14),
+        Day::new::<day15::Solver>(YEAR, 15),
+        Day::new::<day16::Solver>(YEAR, 16),
+        Day::new::<day17::Solver>(YEAR, 17),
+        Day::new::<day18::Solver>(YEAR, 18),
+        Day::new::<day19::Solver>(YEAR, 19),
+        Day::new::<day20::Solver>(YEAR, 20),
+        Day::new::<day21::Solver>(YEAR, 21),
+        Day::new::<day22::Solver>(YEAR, 22),
+        Day::new::<day23::Solver>(YEAR, 23),
+        Day::new::<day24::Solver>(YEAR, 24),
+        Day::new::<day25::Solver>(YEAR, 25),
+    ]
+}
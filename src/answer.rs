@@ -0,0 +1,79 @@
+use std::fmt;
+
+/// The answer to one part of a puzzle.
+///
+/// Every `part1`/`part2`/`solve` return value can be converted into an
+/// `Answer` through `From`, so the runner (and any other tooling) can compare,
+/// sort and serialize answers without parsing them back from strings.
+///
+/// ```
+/// # use advent_solutions::Answer;
+/// assert_eq!(Answer::from(42usize), Answer::Unsigned(42));
+/// assert_eq!(Answer::from(-3isize), Answer::Signed(-3));
+/// assert_eq!(Answer::from("mkxke"), Answer::String("mkxke".to_string()));
+/// assert_eq!(Answer::from(()), Answer::None);
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Answer {
+    /// There is no answer to give (e.g. the free star on the last day).
+    None,
+    Signed(i64),
+    Unsigned(u64),
+    String(String),
+}
+
+impl Answer {
+    /// The name of this answer's variant, in lowercase.
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            Answer::None => "none",
+            Answer::Signed(_) => "signed",
+            Answer::Unsigned(_) => "unsigned",
+            Answer::String(_) => "string",
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Answer::None => Ok(()),
+            Answer::Signed(x) => write!(f, "{}", x),
+            Answer::Unsigned(x) => write!(f, "{}", x),
+            Answer::String(ref x) => write!(f, "{}", x),
+        }
+    }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Answer {
+        Answer::None
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident as $repr:ty: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(x: $t) -> Answer {
+                    Answer::$variant(x as $repr)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Signed as i64: i8, i16, i32, i64, isize);
+answer_from!(Unsigned as u64: u8, u16, u32, u64, usize);
+
+impl<'a> From<&'a str> for Answer {
+    fn from(x: &'a str) -> Answer {
+        Answer::String(x.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(x: String) -> Answer {
+        Answer::String(x)
+    }
+}
@@ -0,0 +1,268 @@
+//! The `bench` command: times every step of the selected days several times
+//! and compares the results with a saved baseline.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+use std::{ fs, io };
+
+use advent_solutions::{ parse, Day };
+use cli::BenchOptions;
+use { catch, get_inputs, profile, select_days, timed, Step };
+
+/// Timing statistics of one step over several runs.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Panics if `times` is empty.
+    pub fn new(times: &[Duration]) -> Stats {
+        let mut times = times.to_vec();
+        times.sort();
+
+        let n = times.len();
+
+        let median = if n % 2 == 1 {
+            times[n / 2]
+        } else {
+            (times[n / 2 - 1] + times[n / 2]) / 2
+        };
+
+        let nanos = times.iter()
+            .map(|x| x.as_nanos() as f64)
+            .collect::<Vec<_>>();
+
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+
+        let variance = nanos.iter()
+            .map(|x| (x - mean).powi(2))
+            .sum::<f64>() / n as f64;
+
+        Stats {
+            min: times[0],
+            median,
+            mean: Duration::from_nanos(mean as u64),
+            stddev: Duration::from_nanos(variance.sqrt() as u64),
+        }
+    }
+}
+
+type Key = (usize, usize, Step);
+
+/// Median times from a previous run, as `YYYY/NN/step: nanoseconds` lines.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Baseline(BTreeMap<Key, Duration>);
+
+impl Baseline {
+    pub fn parse(input: &str) -> Baseline {
+        Baseline(
+            input.lines()
+                .filter_map(|line| {
+                    let colon = line.find(':')?;
+                    let mut key = line[..colon].trim().split('/');
+
+                    let year = key.next()?.parse::<usize>().ok()?;
+                    let day = key.next()?.parse::<usize>().ok()?;
+                    let step = key.next()?.parse::<Step>().ok()?;
+                    let nanos = line[colon + 1..].trim().parse::<u64>().ok()?;
+
+                    Some(((year, day, step), Duration::from_nanos(nanos)))
+                })
+                .collect()
+        )
+    }
+
+    /// Reads the baseline at `path`. A missing file means there is none yet.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Baseline> {
+        match fs::read_to_string(path) {
+            Ok(input) => Ok(Baseline::parse(&input)),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let lines = self.0.iter()
+            .map(|(&(year, day, step), time)| {
+                format!("{}/{:02}/{}: {}\n", year, day, step, time.as_nanos())
+            })
+            .collect::<String>();
+
+        fs::write(path, lines)
+    }
+
+    /// How much slower (in percent) `time` is than the baseline, if known.
+    pub fn delta(&self, key: Key, time: Duration) -> Option<f64> {
+        self.0.get(&key)
+            .filter(|base| base.as_nanos() > 0)
+            .map(|base| (time.as_nanos() as f64 / base.as_nanos() as f64 - 1.0) * 100.0)
+    }
+}
+
+/// Runs `f` `warmup` times and then `runs` more times, timing them.
+fn repeat<T, F: FnMut() -> T>(warmup: usize, runs: usize, mut f: F) -> Result<Vec<Duration>, String> {
+    for _ in 0..warmup {
+        catch(&mut f)?;
+    }
+
+    (0..runs)
+        .map(|_| {
+            let (result, elapsed) = timed(|| catch(&mut f));
+            result.map(|_| elapsed)
+        })
+        .collect()
+}
+
+/// Benchmarks every selected step of `day`. Steps are run one at a time, so
+/// they do not compete with each other. Days which solve both parts together
+/// are timed once for both, as when running them.
+fn bench_day(day: &Day, input: &'static str, options: &BenchOptions) -> Vec<(Step, Result<Stats, String>)> {
+    let stats = |times: Result<Vec<Duration>, String>| times.map(|x| Stats::new(&x));
+
+    let parsed = match catch(|| day.parse_input(input)) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => return vec![(Step::Parse, Err(e.to_string()))],
+        Err(e) => return vec![(Step::Parse, Err(e))],
+    };
+
+    let mut results = vec![
+        (Step::Parse, stats(repeat(options.warmup, options.runs, || day.parse_input(input)))),
+    ];
+
+    if day.solves_both() && options.selection.includes_part(1) && options.selection.includes_part(2) {
+        let times = repeat(options.warmup, options.runs, || day.solve(&parsed));
+
+        results.push((Step::Part1, stats(times.clone())));
+        results.push((Step::Part2, stats(times)));
+
+        return results;
+    }
+
+    for &(step, part) in &[(Step::Part1, 1), (Step::Part2, 2)] {
+        if !options.selection.includes_part(part) {
+            continue;
+        }
+
+        let times = repeat(options.warmup, options.runs, || match step {
+            Step::Part1 => day.part1(&parsed),
+            _ => day.part2(&parsed),
+        });
+
+        results.push((step, stats(times)));
+    }
+
+    results
+}
+
+pub fn run(options: BenchOptions) -> Result<bool, String> {
+    let days = select_days(&options.selection)?;
+    let profile = profile(&options.inputs)?;
+    let inputs = get_inputs(&options.inputs, profile.as_ref(), days.keys().cloned().collect())?
+        .iter()
+        .collect::<BTreeMap<_, _>>();
+
+    let baseline = Baseline::load(&options.baseline)
+        .map_err(|e| format!("Error reading baseline: {}", e))?;
+
+    let mut medians = Baseline::default();
+    let mut ok = true;
+
+    println!(
+        "Day  {:<5} {:>12} {:>12} {:>12} {:>12}  Baseline",
+        "Step", "Min", "Median", "Mean", "Stddev"
+    );
+
+    for ((year, day), input) in inputs {
+        let input: &'static str = match input {
+            Ok(input) => Box::leak(parse::normalize(&input).into_boxed_str()),
+            Err(e) => {
+                println!("{:02}   FAILED: input: {}", day, e);
+                ok = false;
+                continue;
+            },
+        };
+
+        for (step, stats) in bench_day(&days[&(year, day)], input, &options) {
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(e) => {
+                    println!("{:02}   {:<5} FAILED: {}", day, step.to_string(), e);
+                    ok = false;
+                    continue;
+                },
+            };
+
+            let key = (year, day, step);
+
+            let delta = match baseline.delta(key, stats.median) {
+                Some(delta) if delta > options.threshold as f64 => {
+                    ok = false;
+                    format!("{:+.1}% REGRESSED", delta)
+                },
+                Some(delta) => format!("{:+.1}%", delta),
+                None => String::new(),
+            };
+
+            println!(
+                "{:02}   {:<5} {:>12} {:>12} {:>12} {:>12}  {}",
+                day,
+                step.to_string(),
+                format!("{:?}", stats.min),
+                format!("{:?}", stats.median),
+                format!("{:?}", stats.mean),
+                format!("{:?}", stats.stddev),
+                delta,
+            );
+
+            medians.0.insert(key, stats.median);
+        }
+    }
+
+    if options.save {
+        // Keep the baseline for anything that was not run this time.
+        let mut saved = baseline;
+        saved.0.extend(medians.0);
+
+        saved.save(&options.baseline)
+            .map_err(|e| format!("Error saving baseline: {}", e))?;
+
+        eprintln!("Baseline saved to {}", options.baseline);
+    }
+
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn stats() {
+        let stats = Stats::new(&[ms(4), ms(2), ms(6), ms(8)]);
+
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(5));
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(2_236_067));
+
+        assert_eq!(Stats::new(&[ms(3), ms(1), ms(2)]).median, ms(2));
+    }
+
+    #[test]
+    fn baseline() {
+        let baseline = Baseline::parse("2017/03/parse: 1000\n2017/03/1: 2000\nnonsense\n");
+
+        assert_eq!(baseline.delta((2017, 3, Step::Parse), Duration::from_nanos(1500)), Some(50.0));
+        assert_eq!(baseline.delta((2017, 3, Step::Part1), Duration::from_nanos(1000)), Some(-50.0));
+        assert_eq!(baseline.delta((2017, 3, Step::Part2), Duration::from_nanos(1000)), None);
+    }
+}
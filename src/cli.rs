@@ -0,0 +1,626 @@
+//! Command-line argument parsing.
+
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const USAGE: &str = "\
+Usage: advent_solutions [COMMAND] [OPTIONS]
+
+Commands:
+    run               Run the selected solutions (all of them by default) on
+                      your inputs (default)
+    bench             Time the selected solutions on your inputs
+    watch             Run a day again whenever its module or its input
+                      changes (requires --day)
+    new               Create the module for a new day (requires --year,
+                      --day and --name)
+    puzzle            Print a day's description as doc comments (requires
+                      --year and --day)
+    leaderboard       Show a private leaderboard (requires --year and --id)
+    help              Show this help
+
+Selection:
+    --year YEARS      Years to run, e.g. `2017`, `2015..2017` or `2015,2017`
+    --day DAYS        Days to run, e.g. `3`, `3..7` (inclusive) or `1,3,5`
+    --part PART       Only run part `1` or `2`
+
+Inputs:
+    --input PATH      Read the input from PATH (`-` for stdin) instead of
+                      downloading it (requires selecting a single day)
+    --cache DIR       Directory inputs are cached in (default: inputs)
+    --refresh         Download inputs again even if they are cached
+    --downloads N     Maximum concurrent downloads (default: 2)
+    --delay MS        Minimum delay between requests (default: 1000)
+    --profile NAME    Use the session of a profile in the config file, with
+                      its own inputs cache and answers (see below)
+
+Output (run):
+    --answers DIR     Directory with YYYY.txt answers files (default: answers,
+                      or the profile's)
+    --format FORMAT   Output `text` (default), `json` or `csv`
+    --timeout SECS    Report steps that take longer than this as TIMEOUT
+                      (default: no timeout)
+
+Benchmark (bench):
+    --runs N          Timed runs of each step (default: 10)
+    --warmup N        Untimed runs before those (default: 3)
+    --baseline PATH   Baseline to compare against (default: baseline.txt)
+    --save            Save the results as the new baseline
+    --threshold PCT   Flag medians slower than the baseline by more than PCT
+                      percent as regressions (default: 10)
+
+Watch (watch):
+    --interval MS     How often to check for changes (default: 500)
+    --answers DIR     Directory with YYYY.txt answers files, as for run
+    --timeout SECS    Report steps that take longer than this as TIMEOUT
+                      (default: 60, at least 1), killing runs that take 3
+                      times that
+
+New day (new, puzzle):
+    --year YEAR       The puzzle's year
+    --day DAY         The puzzle's day
+    --name NAME       Name for the module (e.g. `inverse_captcha`, new only)
+
+Leaderboard (leaderboard):
+    --year YEAR       The leaderboard's year
+    --id ID           The leaderboard's ID (the number in its URL)
+    --day DAYS        Only show these days
+    --profile NAME    Use the session of a profile (and its cache)
+    --cache DIR       Directory leaderboards are cached in (default: inputs)
+
+    -h, --help        Show this help
+
+Profiles are read from $XDG_CONFIG_HOME/advent_solutions/config (by default
+~/.config/advent_solutions/config), with one section per profile:
+
+    [alice]
+    session = 53616c7465645f5f...
+    cache = inputs/alice         # default
+    answers = answers/alice      # default
+";
+
+/// Which solutions to run. `None` means all of them.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Selection {
+    pub years: Option<Vec<usize>>,
+    pub days: Option<Vec<usize>>,
+    pub part: Option<usize>,
+}
+
+impl Selection {
+    pub fn includes_part(&self, part: usize) -> bool {
+        self.part.is_none_or(|x| x == part)
+    }
+}
+
+/// Where to get inputs from.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct InputOptions {
+    pub input: Option<String>,
+    pub cache_dir: Option<String>,
+    pub refresh: bool,
+    pub downloads: Option<usize>,
+    pub delay: Option<Duration>,
+    pub profile: Option<String>,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Format { Text, Json, Csv }
+
+impl FromStr for Format {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RunOptions {
+    pub selection: Selection,
+    pub inputs: InputOptions,
+    pub answers_dir: Option<String>,
+    pub format: Format,
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BenchOptions {
+    pub selection: Selection,
+    pub inputs: InputOptions,
+    pub runs: usize,
+    pub warmup: usize,
+    pub baseline: String,
+    pub save: bool,
+    /// In percent.
+    pub threshold: u32,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WatchOptions {
+    pub selection: Selection,
+    pub inputs: InputOptions,
+    pub interval: Duration,
+    pub answers_dir: Option<String>,
+    pub timeout: Duration,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LeaderboardOptions {
+    pub year: usize,
+    pub id: u64,
+    pub days: Option<Vec<usize>>,
+    /// Only the profile and cache directory are used.
+    pub inputs: InputOptions,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NewOptions {
+    pub year: usize,
+    pub day: usize,
+    pub name: String,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PuzzleOptions {
+    pub year: usize,
+    pub day: usize,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Command {
+    Run(RunOptions),
+    Bench(BenchOptions),
+    Watch(WatchOptions),
+    New(NewOptions),
+    Puzzle(PuzzleOptions),
+    Leaderboard(LeaderboardOptions),
+    Help,
+}
+
+/// Every Advent of Code day.
+const DAYS: RangeInclusive<usize> = 1..=25;
+
+/// Years that could have an Advent of Code (the first was in 2015).
+const YEARS: RangeInclusive<usize> = 2015..=2099;
+
+/// Parses `1`, `1..3` (inclusive) or `1,3,5` (or any mix of them, like
+/// `1..3,5`), all of which must be in `valid`.
+fn parse_numbers(flag: &str, value: &str, valid: RangeInclusive<usize>) -> Result<Vec<usize>, String> {
+    let invalid = || format!("Invalid {} `{}`", flag, value);
+
+    let mut numbers = vec![];
+
+    for range in value.split(',') {
+        let mut bounds = range.splitn(2, "..");
+
+        let start = bounds.next()
+            .and_then(|x| x.trim().parse::<usize>().ok())
+            .ok_or_else(invalid)?;
+
+        let end = match bounds.next() {
+            Some(end) => end.trim_start_matches('=').trim().parse::<usize>().map_err(|_| invalid())?,
+            None => start,
+        };
+
+        if end < start {
+            return Err(invalid());
+        }
+
+        if !valid.contains(&start) || !valid.contains(&end) {
+            return Err(format!("{} (must be from {} to {})", invalid(), valid.start(), valid.end()));
+        }
+
+        numbers.extend(start..=end);
+    }
+
+    numbers.sort();
+    numbers.dedup();
+
+    Ok(numbers)
+}
+
+/// Command-line arguments, supporting both `--flag value` and
+/// `--flag=value`.
+pub struct Args<I: Iterator<Item=String>> {
+    args: I,
+    value: Option<String>,
+}
+
+impl<I: Iterator<Item=String>> Args<I> {
+    pub fn new(args: I) -> Args<I> {
+        Args { args, value: None }
+    }
+
+    /// Returns the next flag (or positional argument).
+    pub fn next_flag(&mut self) -> Result<Option<String>, String> {
+        if let Some(value) = self.value.take() {
+            return Err(format!("Unexpected value `{}`", value));
+        }
+
+        Ok(self.args.next().map(|arg| {
+            if arg.starts_with("--") {
+                if let Some(eq) = arg.find('=') {
+                    self.value = Some(arg[eq + 1..].to_string());
+                    return arg[..eq].to_string();
+                }
+            }
+
+            arg
+        }))
+    }
+
+    /// Returns the value for `flag`.
+    pub fn value(&mut self, flag: &str) -> Result<String, String> {
+        self.value.take()
+            .or_else(|| self.args.next())
+            .ok_or_else(|| format!("Missing value for {}", flag))
+    }
+
+    /// Parses the value for `flag`.
+    pub fn parse<T: FromStr>(&mut self, flag: &str) -> Result<T, String> {
+        let value = self.value(flag)?;
+
+        value.parse::<T>()
+            .map_err(|_| format!("Invalid {} `{}`", flag, value))
+    }
+}
+
+/// Parses a selection flag into `selection`, returning whether `flag` was one.
+pub fn parse_selection<I>(
+    flag: &str,
+    args: &mut Args<I>,
+    selection: &mut Selection,
+) -> Result<bool, String>
+    where I: Iterator<Item=String>,
+{
+    match flag {
+        "--year" => selection.years = Some(parse_numbers(flag, &args.value(flag)?, YEARS)?),
+        "--day" => selection.days = Some(parse_numbers(flag, &args.value(flag)?, DAYS)?),
+        "--part" => match args.parse::<usize>(flag)? {
+            part @ 1 | part @ 2 => selection.part = Some(part),
+            part => return Err(format!("Invalid --part `{}` (must be 1 or 2)", part)),
+        },
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Parses an input flag into `inputs`, returning whether `flag` was one.
+pub fn parse_inputs<I>(
+    flag: &str,
+    args: &mut Args<I>,
+    inputs: &mut InputOptions,
+) -> Result<bool, String>
+    where I: Iterator<Item=String>,
+{
+    match flag {
+        "--input" => inputs.input = Some(args.value(flag)?),
+        "--cache" => inputs.cache_dir = Some(args.value(flag)?),
+        "--refresh" => inputs.refresh = true,
+        "--downloads" => inputs.downloads = Some(args.parse(flag)?),
+        "--delay" => inputs.delay = Some(Duration::from_millis(args.parse(flag)?)),
+        "--profile" => inputs.profile = Some(args.value(flag)?),
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn parse_run<I: Iterator<Item=String>>(mut args: Args<I>) -> Result<Command, String> {
+    let mut options = RunOptions {
+        selection: Selection::default(),
+        inputs: InputOptions::default(),
+        answers_dir: None,
+        format: Format::Text,
+        timeout: None,
+    };
+
+    while let Some(flag) = args.next_flag()? {
+        if parse_selection(&flag, &mut args, &mut options.selection)?
+            || parse_inputs(&flag, &mut args, &mut options.inputs)? {
+            continue;
+        }
+
+        match flag.as_str() {
+            "--answers" => options.answers_dir = Some(args.value(&flag)?),
+            "--format" => options.format = args.parse(&flag)?,
+            "--timeout" => options.timeout = Some(Duration::from_secs(args.parse(&flag)?)),
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("Unknown argument `{}`", flag)),
+        }
+    }
+
+    Ok(Command::Run(options))
+}
+
+fn parse_bench<I: Iterator<Item=String>>(mut args: Args<I>) -> Result<Command, String> {
+    let mut options = BenchOptions {
+        selection: Selection::default(),
+        inputs: InputOptions::default(),
+        runs: 10,
+        warmup: 3,
+        baseline: "baseline.txt".to_string(),
+        save: false,
+        threshold: 10,
+    };
+
+    while let Some(flag) = args.next_flag()? {
+        if parse_selection(&flag, &mut args, &mut options.selection)?
+            || parse_inputs(&flag, &mut args, &mut options.inputs)? {
+            continue;
+        }
+
+        match flag.as_str() {
+            "--runs" => options.runs = args.parse(&flag)?,
+            "--warmup" => options.warmup = args.parse(&flag)?,
+            "--baseline" => options.baseline = args.value(&flag)?,
+            "--save" => options.save = true,
+            "--threshold" => options.threshold = args.parse(&flag)?,
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("Unknown argument `{}`", flag)),
+        }
+    }
+
+    if options.runs == 0 {
+        return Err("--runs must be at least 1".to_string());
+    }
+
+    Ok(Command::Bench(options))
+}
+
+fn parse_watch<I: Iterator<Item=String>>(mut args: Args<I>) -> Result<Command, String> {
+    let mut options = WatchOptions {
+        selection: Selection::default(),
+        inputs: InputOptions::default(),
+        interval: Duration::from_millis(500),
+        answers_dir: None,
+        timeout: Duration::from_secs(60),
+    };
+
+    while let Some(flag) = args.next_flag()? {
+        if parse_selection(&flag, &mut args, &mut options.selection)?
+            || parse_inputs(&flag, &mut args, &mut options.inputs)? {
+            continue;
+        }
+
+        match flag.as_str() {
+            "--interval" => options.interval = Duration::from_millis(args.parse(&flag)?),
+            "--answers" => options.answers_dir = Some(args.value(&flag)?),
+            "--timeout" => options.timeout = Duration::from_secs(args.parse(&flag)?),
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("Unknown argument `{}`", flag)),
+        }
+    }
+
+    // Runs are killed after 3 times the timeout, so none would ever finish.
+    if options.timeout == Duration::default() {
+        return Err("--timeout must be at least 1".to_string());
+    }
+
+    match options.selection.days {
+        Some(ref days) if days.len() == 1 => {},
+        _ => return Err("watch requires selecting a single --day".to_string()),
+    }
+
+    if options.selection.years.as_ref().is_some_and(|years| years.len() != 1) {
+        return Err("watch requires selecting a single --year".to_string());
+    }
+
+    Ok(Command::Watch(options))
+}
+
+/// Parses the options for `new` (with `--name`) or `puzzle` (without it).
+fn parse_new<I: Iterator<Item=String>>(mut args: Args<I>, new: bool) -> Result<Command, String> {
+    let (mut year, mut day, mut name) = (None, None, None);
+
+    while let Some(flag) = args.next_flag()? {
+        match flag.as_str() {
+            "--year" => year = Some(args.parse(&flag)?),
+            "--day" => day = Some(args.parse(&flag)?),
+            "--name" if new => name = Some(args.value(&flag)?),
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("Unknown argument `{}`", flag)),
+        }
+    }
+
+    let year = year.ok_or("Missing --year")?;
+    let day = day.ok_or("Missing --day")?;
+
+    if !new {
+        return Ok(Command::Puzzle(PuzzleOptions { year, day }));
+    }
+
+    Ok(Command::New(NewOptions { year, day, name: name.ok_or("Missing --name")? }))
+}
+
+fn parse_leaderboard<I: Iterator<Item=String>>(mut args: Args<I>) -> Result<Command, String> {
+    let (mut year, mut id, mut days) = (None, None, None);
+    let mut inputs = InputOptions::default();
+
+    while let Some(flag) = args.next_flag()? {
+        match flag.as_str() {
+            "--year" => year = Some(args.parse(&flag)?),
+            "--id" => id = Some(args.parse(&flag)?),
+            "--day" => days = Some(parse_numbers(&flag, &args.value(&flag)?, DAYS)?),
+            "--profile" => inputs.profile = Some(args.value(&flag)?),
+            "--cache" => inputs.cache_dir = Some(args.value(&flag)?),
+            "-h" | "--help" => return Ok(Command::Help),
+            _ => return Err(format!("Unknown argument `{}`", flag)),
+        }
+    }
+
+    Ok(Command::Leaderboard(LeaderboardOptions {
+        year: year.ok_or("Missing --year")?,
+        id: id.ok_or("Missing --id")?,
+        days,
+        inputs,
+    }))
+}
+
+/// Parses the command-line arguments (without the program name).
+pub fn parse<I: Iterator<Item=String>>(args: I) -> Result<Command, String> {
+    let mut args = args.peekable();
+
+    let command = match args.peek() {
+        Some(arg) if !arg.starts_with('-') => Some(arg.clone()),
+        _ => None,
+    };
+
+    match command.as_deref() {
+        None => parse_run(Args::new(args)),
+        Some("run") => parse_run(Args::new(args.skip(1))),
+        Some("bench") => parse_bench(Args::new(args.skip(1))),
+        Some("watch") => parse_watch(Args::new(args.skip(1))),
+        Some("new") => parse_new(Args::new(args.skip(1)), true),
+        Some("puzzle") => parse_new(Args::new(args.skip(1)), false),
+        Some("leaderboard") => parse_leaderboard(Args::new(args.skip(1))),
+        Some("help") => Ok(Command::Help),
+        Some(command) => Err(format!("Unknown command `{}`", command)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(args: &[&str]) -> Result<RunOptions, String> {
+        match parse(args.iter().map(|x| x.to_string()))? {
+            Command::Run(options) => Ok(options),
+            command => panic!("Unexpected {:?}", command),
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_numbers("--day", "3", DAYS), Ok(vec![3]));
+        assert_eq!(parse_numbers("--day", "3..7", DAYS), Ok(vec![3, 4, 5, 6, 7]));
+        assert_eq!(parse_numbers("--day", "3..=5", DAYS), Ok(vec![3, 4, 5]));
+        assert_eq!(parse_numbers("--day", "5,1..2,1", DAYS), Ok(vec![1, 2, 5]));
+        assert_eq!(parse_numbers("--year", "2017..2018", YEARS), Ok(vec![2017, 2018]));
+        assert!(parse_numbers("--day", "7..3", DAYS).is_err());
+        assert!(parse_numbers("--day", "day03", DAYS).is_err());
+        assert!(parse_numbers("--day", "0", DAYS).is_err());
+        assert!(parse_numbers("--day", "1..99999999999", DAYS).is_err());
+        assert!(parse_numbers("--day", &format!("1..{}", usize::MAX), DAYS).is_err());
+        assert!(parse_numbers("--year", "17", YEARS).is_err());
+    }
+
+    #[test]
+    fn run_options() {
+        let options = run(&["run", "--year", "2017", "--day=3..4", "--part", "2", "--input", "-"])
+            .unwrap();
+
+        assert_eq!(options.selection, Selection {
+            years: Some(vec![2017]),
+            days: Some(vec![3, 4]),
+            part: Some(2),
+        });
+        assert_eq!(options.inputs.input, Some("-".to_string()));
+        assert_eq!(options.answers_dir, None);
+        assert_eq!(options.format, Format::Text);
+        assert_eq!(options.timeout, None);
+        assert_eq!(run(&["--format", "json"]).unwrap().format, Format::Json);
+        assert_eq!(run(&["--timeout", "10"]).unwrap().timeout, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn default_command() {
+        assert_eq!(run(&[]).unwrap().selection, Selection::default());
+        assert!(run(&["--refresh"]).unwrap().inputs.refresh);
+        assert_eq!(run(&["--profile", "alice"]).unwrap().inputs.profile, Some("alice".to_string()));
+    }
+
+    #[test]
+    fn bench_options() {
+        let args = ["bench", "--day", "5", "--runs=20", "--warmup", "0", "--save", "--threshold", "25"];
+
+        match parse(args.iter().map(|x| x.to_string())).unwrap() {
+            Command::Bench(options) => {
+                assert_eq!(options.selection.days, Some(vec![5]));
+                assert_eq!(options.runs, 20);
+                assert_eq!(options.warmup, 0);
+                assert_eq!(options.baseline, "baseline.txt");
+                assert!(options.save);
+                assert_eq!(options.threshold, 25);
+            },
+            command => panic!("Unexpected {:?}", command),
+        }
+
+        assert!(parse(vec!["bench".to_string(), "--runs=0".to_string()].into_iter()).is_err());
+    }
+
+    #[test]
+    fn watch_options() {
+        let args = |args: &[&str]| parse(args.iter().map(|x| x.to_string()));
+
+        match args(&["watch", "--day", "3", "--timeout", "5", "--input", "x", "--answers", "a"]).unwrap() {
+            Command::Watch(options) => {
+                assert_eq!(options.selection.days, Some(vec![3]));
+                assert_eq!(options.inputs.input, Some("x".to_string()));
+                assert_eq!(options.interval, Duration::from_millis(500));
+                assert_eq!(options.answers_dir, Some("a".to_string()));
+                assert_eq!(options.timeout, Duration::from_secs(5));
+            },
+            command => panic!("Unexpected {:?}", command),
+        }
+
+        assert!(args(&["watch"]).is_err());
+        assert!(args(&["watch", "--day", "3", "--timeout", "0"]).is_err());
+        assert!(args(&["watch", "--day", "3..4"]).is_err());
+        assert!(args(&["watch", "--day", "3", "--year", "2015,2017"]).is_err());
+    }
+
+    #[test]
+    fn new_options() {
+        let args = |args: &[&str]| parse(args.iter().map(|x| x.to_string()));
+
+        assert_eq!(
+            args(&["new", "--year", "2017", "--day", "1", "--name", "inverse_captcha"]),
+            Ok(Command::New(NewOptions { year: 2017, day: 1, name: "inverse_captcha".to_string() }))
+        );
+        assert!(args(&["new", "--year", "2017", "--day", "1"]).is_err());
+        assert!(args(&["new", "--year", "2017", "--day", "1..3", "--name", "x"]).is_err());
+
+        assert_eq!(
+            args(&["puzzle", "--year", "2017", "--day", "1"]),
+            Ok(Command::Puzzle(PuzzleOptions { year: 2017, day: 1 }))
+        );
+        assert!(args(&["puzzle", "--year", "2017", "--day", "1", "--name", "x"]).is_err());
+    }
+
+    #[test]
+    fn leaderboard_options() {
+        let args = |args: &[&str]| parse(args.iter().map(|x| x.to_string()));
+
+        match args(&["leaderboard", "--year", "2017", "--id", "1001", "--day", "1..3", "--profile", "alice"]) {
+            Ok(Command::Leaderboard(options)) => {
+                assert_eq!((options.year, options.id), (2017, 1001));
+                assert_eq!(options.days, Some(vec![1, 2, 3]));
+                assert_eq!(options.inputs.profile, Some("alice".to_string()));
+            },
+            command => panic!("Unexpected {:?}", command),
+        }
+
+        assert!(args(&["leaderboard", "--year", "2017"]).is_err());
+        assert!(args(&["leaderboard", "--id", "1001"]).is_err());
+        assert!(args(&["leaderboard", "--year", "2017", "--id", "1001", "--refresh"]).is_err());
+    }
+
+    #[test]
+    fn errors() {
+        assert!(run(&["--part", "3"]).is_err());
+        assert!(run(&["--day"]).is_err());
+        assert!(run(&["--refresh=yes"]).is_err());
+        assert!(run(&["--frobnicate"]).is_err());
+        assert!(run(&["--format", "xml"]).is_err());
+        assert!(parse(vec!["frobnicate".to_string()].into_iter()).is_err());
+    }
+}
@@ -0,0 +1,234 @@
+//! The configuration file, with named profiles for machines shared by several
+//! Advent of Code accounts.
+//!
+//! It lives in `$XDG_CONFIG_HOME/advent_solutions/config` (by default
+//! `~/.config/advent_solutions/config`) and looks like this:
+//!
+//! ```text
+//! # Inputs go to inputs/alice and answers are read from answers/alice.
+//! [alice]
+//! session = 53616c7465645f5f...
+//!
+//! [bob]
+//! session = 53616c7465645f5f...
+//! cache = /home/bob/advent/inputs
+//! answers = /home/bob/advent/answers
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::{ Path, PathBuf };
+use std::{ env, error, fmt, fs, io };
+
+/// Everything that can go wrong while reading the configuration.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read.
+    Io(PathBuf, io::Error),
+    /// A line of the file makes no sense.
+    Syntax { line: usize, message: String },
+    /// There is no profile with this name.
+    UnknownProfile(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::Io(ref path, ref e) => write!(f, "{}: {}", path.display(), e),
+            ConfigError::Syntax { line, ref message } => write!(
+                f,
+                "Invalid configuration (line {}): {}",
+                line,
+                message
+            ),
+            ConfigError::UnknownProfile(ref name) => write!(
+                f,
+                "No profile `{}` in {}",
+                name,
+                Config::path().map(|x| x.display().to_string()).unwrap_or_default()
+            ),
+        }
+    }
+}
+
+impl error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ConfigError::Io(_, ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// One Advent of Code account.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Profile {
+    pub name: String,
+    /// The session cookie, if any (without it only cached inputs can be
+    /// used).
+    pub session: Option<String>,
+    /// Where inputs are cached (`inputs/<name>` by default).
+    pub cache_dir: PathBuf,
+    /// Where answers are read from (`answers/<name>` by default).
+    pub answers_dir: PathBuf,
+}
+
+impl Profile {
+    fn new(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            session: None,
+            cache_dir: Path::new("inputs").join(name),
+            answers_dir: Path::new("answers").join(name),
+        }
+    }
+}
+
+/// The profiles in the configuration file.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Config {
+    profiles: BTreeMap<String, Profile>,
+}
+
+impl Config {
+    /// Where the configuration file is, if there is a home directory to put it
+    /// in.
+    pub fn path() -> Option<PathBuf> {
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+        Some(config_home.join("advent_solutions").join("config"))
+    }
+
+    /// Reads the configuration file. A missing file means no profiles.
+    pub fn load() -> Result<Config, ConfigError> {
+        match Config::path() {
+            Some(path) => Config::load_from(path),
+            None => Ok(Config::default()),
+        }
+    }
+
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Config, ConfigError> {
+        let path = path.as_ref();
+
+        match fs::read_to_string(path) {
+            Ok(input) => Config::parse(&input),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(ConfigError::Io(path.to_path_buf(), e)),
+        }
+    }
+
+    /// Parses `[name]` sections of `key = value` lines. Empty lines and lines
+    /// starting with `#` are ignored.
+    pub fn parse(input: &str) -> Result<Config, ConfigError> {
+        let mut profiles = BTreeMap::new();
+        let mut current: Option<Profile> = None;
+
+        for (i, line) in input.lines().enumerate() {
+            let line = line.trim();
+            let error = |message: String| ConfigError::Syntax { line: i + 1, message };
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if line.starts_with('[') && line.ends_with(']') {
+                let name = line[1..line.len() - 1].trim();
+
+                if name.is_empty() || name.contains(&['/', '\\'][..]) || name.starts_with('.') {
+                    return Err(error(format!("Invalid profile name `{}`", name)));
+                }
+
+                if profiles.contains_key(name) || current.as_ref().is_some_and(|x| x.name == name) {
+                    return Err(error(format!("Duplicate profile `{}`", name)));
+                }
+
+                if let Some(profile) = current.replace(Profile::new(name)) {
+                    profiles.insert(profile.name.clone(), profile);
+                }
+
+                continue;
+            }
+
+            let profile = current.as_mut()
+                .ok_or_else(|| error("Expected a `[profile]` first".to_string()))?;
+
+            let eq = line.find('=')
+                .ok_or_else(|| error(format!("Expected `key = value`, found `{}`", line)))?;
+
+            let value = line[eq + 1..].trim().to_string();
+
+            match line[..eq].trim() {
+                "session" => profile.session = Some(value),
+                "cache" => profile.cache_dir = PathBuf::from(value),
+                "answers" => profile.answers_dir = PathBuf::from(value),
+                key => return Err(error(format!("Unknown key `{}`", key))),
+            }
+        }
+
+        if let Some(profile) = current {
+            profiles.insert(profile.name.clone(), profile);
+        }
+
+        Ok(Config { profiles })
+    }
+
+    pub fn profile(&self, name: &str) -> Result<&Profile, ConfigError> {
+        self.profiles.get(name)
+            .ok_or_else(|| ConfigError::UnknownProfile(name.to_string()))
+    }
+
+    pub fn profiles(&self) -> impl Iterator<Item=&Profile> {
+        self.profiles.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profiles() {
+        let config = Config::parse("
+            # Comment
+            [alice]
+            session = abc=123
+
+            [bob]
+            cache = /tmp/bob
+            answers=/tmp/bob-answers
+        ").unwrap();
+
+        assert_eq!(config.profiles().map(|x| x.name.as_str()).collect::<Vec<_>>(), vec!["alice", "bob"]);
+
+        assert_eq!(config.profile("alice").unwrap(), &Profile {
+            name: "alice".to_string(),
+            session: Some("abc=123".to_string()),
+            cache_dir: PathBuf::from("inputs/alice"),
+            answers_dir: PathBuf::from("answers/alice"),
+        });
+
+        let bob = config.profile("bob").unwrap();
+        assert_eq!(bob.session, None);
+        assert_eq!(bob.cache_dir, PathBuf::from("/tmp/bob"));
+        assert_eq!(bob.answers_dir, PathBuf::from("/tmp/bob-answers"));
+
+        assert!(config.profile("carol").is_err());
+    }
+
+    #[test]
+    fn errors() {
+        let line = |input: &str| match Config::parse(input) {
+            Err(ConfigError::Syntax { line, .. }) => line,
+            result => panic!("Unexpected {:?}", result),
+        };
+
+        assert_eq!(line("session = abc"), 1);
+        assert_eq!(line("[alice]\nsession abc"), 2);
+        assert_eq!(line("[alice]\ncolor = blue"), 2);
+        assert_eq!(line("[alice]\n[alice]"), 2);
+        assert_eq!(line("[../alice]"), 1);
+        assert_eq!(line("[]"), 1);
+    }
+}
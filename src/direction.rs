@@ -1,5 +1,3 @@
-use std::ops;
-
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
 pub enum Direction { Up, Right, Down, Left }
 
@@ -55,96 +53,3 @@ impl<'a> Into<(isize, isize)> for &'a Direction {
         }
     }
 }
-
-impl ops::Add<(isize, isize)> for Direction {
-    type Output = (isize, isize);
-
-    fn add(self, (x, y): (isize, isize)) -> Self::Output {
-        let (dx, dy) = self.into();
-        (x + dx, y + dy)
-    }
-}
-
-impl<'a> ops::Add<(isize, isize)> for &'a Direction {
-    type Output = (isize, isize);
-
-    fn add(self, o: (isize, isize)) -> Self::Output {
-        *self + o
-    }
-}
-
-impl ops::Add<(usize, usize)> for Direction {
-    type Output = (usize, usize);
-
-    fn add(self, (x, y): (usize, usize)) -> Self::Output {
-        let (dx, dy) = self.into();
-        (
-            (x as isize + dx) as usize,
-            (y as isize + dy) as usize,
-        )
-    }
-}
-
-impl<'a> ops::Add<(usize, usize)> for &'a Direction {
-    type Output = (usize, usize);
-
-    fn add(self, o: (usize, usize)) -> Self::Output {
-        *self + o
-    }
-}
-
-impl ops::Add<Direction> for (isize, isize) {
-    type Output = (isize, isize);
-
-    fn add(self, facing: Direction) -> Self::Output {
-        facing + self
-    }
-}
-
-impl<'a> ops::Add<&'a Direction> for (isize, isize) {
-    type Output = (isize, isize);
-
-    fn add(self, facing: &'a Direction) -> Self::Output {
-        facing + self
-    }
-}
-
-impl ops::Add<Direction> for (usize, usize) {
-    type Output = (usize, usize);
-
-    fn add(self, facing: Direction) -> Self::Output {
-        facing + self
-    }
-}
-
-impl<'a> ops::Add<&'a Direction> for (usize, usize) {
-    type Output = (usize, usize);
-
-    fn add(self, facing: &'a Direction) -> Self::Output {
-        facing + self
-    }
-}
-
-impl ops::AddAssign<Direction> for (isize, isize) {
-    fn add_assign(&mut self, other: Direction) {
-        *self = *self + other
-    }
-}
-
-impl<'a> ops::AddAssign<&'a Direction> for (isize, isize) {
-    fn add_assign(&mut self, other: &'a Direction) {
-        *self = *self + other
-    }
-}
-
-impl ops::AddAssign<Direction> for (usize, usize) {
-    fn add_assign(&mut self, other: Direction) {
-        *self = *self + other
-    }
-}
-
-impl<'a> ops::AddAssign<&'a Direction> for (usize, usize) {
-    fn add_assign(&mut self, other: &'a Direction) {
-        *self = *self + other
-    }
-}
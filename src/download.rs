@@ -1,53 +1,652 @@
-use reqwest::Client;
-use std::io::Read;
-use std::{env, fs, io};
+use reqwest::{ Client, StatusCode };
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{ mpsc, Arc, Mutex };
+use std::time::{ Duration, Instant, SystemTime };
+use std::{cmp, env, error, fmt, fs, io, thread};
 
+use { Answer, Leaderboard, Profile, Puzzle };
+
+/// Everything that can go wrong while getting a puzzle input.
+#[derive(Debug)]
+pub enum DownloadError {
+    /// No `SESSION` environment variable or file (or profile session) was
+    /// found.
+    MissingSession,
+    /// The server answered with an unsuccessful status code.
+    Http { status: StatusCode, body: String },
+    /// The request could not be completed.
+    Transport(::reqwest::Error),
+    /// The puzzle for this day is not available yet.
+    NotUnlocked { year: usize, day: usize },
+    /// The session file or the input cache could not be read or written.
+    Io(io::Error),
+    /// The server answered with something we don't understand.
+    UnexpectedResponse(String),
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DownloadError::MissingSession => write!(
+                f,
+                "SESSION environment variable or file (or a profile session) is required \
+                 (your session cookie)"
+            ),
+            DownloadError::Http { status, ref body } => write!(
+                f,
+                "HTTP error ({}): {}",
+                status,
+                body.trim()
+            ),
+            DownloadError::Transport(ref e) => write!(f, "Error sending request: {}", e),
+            DownloadError::NotUnlocked { year, day } => write!(
+                f,
+                "Day {} of {} is not unlocked yet",
+                day,
+                year
+            ),
+            DownloadError::Io(ref e) => write!(f, "I/O error: {}", e),
+            DownloadError::UnexpectedResponse(ref body) => write!(
+                f,
+                "Unexpected response: {}",
+                body.trim()
+            ),
+        }
+    }
+}
+
+impl error::Error for DownloadError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            DownloadError::Transport(ref e) => Some(e),
+            DownloadError::Io(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<::reqwest::Error> for DownloadError {
+    fn from(e: ::reqwest::Error) -> DownloadError {
+        DownloadError::Transport(e)
+    }
+}
+
+impl From<io::Error> for DownloadError {
+    fn from(e: io::Error) -> DownloadError {
+        DownloadError::Io(e)
+    }
+}
+
+/// Whether a submitted answer was too high or too low.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Hint { TooHigh, TooLow }
+
+/// The outcome of submitting an answer.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Submission {
+    Correct,
+    Incorrect { hint: Option<Hint> },
+    /// An answer was submitted too recently; try again after `wait`.
+    RateLimited { wait: Duration },
+    /// This part was already solved (or part 1 is not solved yet).
+    AlreadySolved,
+}
+
+impl Submission {
+    fn parse(body: &str) -> Option<Submission> {
+        if body.contains("That's the right answer") {
+            Some(Submission::Correct)
+        } else if body.contains("That's not the right answer") {
+            let hint = if body.contains("too high") {
+                Some(Hint::TooHigh)
+            } else if body.contains("too low") {
+                Some(Hint::TooLow)
+            } else {
+                None
+            };
+
+            Some(Submission::Incorrect { hint })
+        } else if body.contains("You gave an answer too recently") {
+            Some(Submission::RateLimited { wait: parse_wait(body)? })
+        } else if body.contains("Did you already complete it?") {
+            Some(Submission::AlreadySolved)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parses the `You have 1m 23s left to wait` bit of a rate-limited response.
+fn parse_wait(body: &str) -> Option<Duration> {
+    let start = body.find("You have ")? + "You have ".len();
+    let end = start + body[start..].find(" left to wait")?;
+
+    body[start..end].split_whitespace()
+        .map(|x| {
+            let (n, unit) = x.split_at(x.len() - 1);
+            let n = n.parse::<u64>().ok()?;
+
+            match unit {
+                "h" => Some(n * 3600),
+                "m" => Some(n * 60),
+                "s" => Some(n),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// How long a downloaded leaderboard is used before downloading it again.
+const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// Downloads puzzle inputs, caching them on disk under
+/// `<cache dir>/<year>/<day>` (e.g. `inputs/2017/03`).
+///
+/// Requests are spaced at least `delay` apart (1 second by default), even when
+/// downloading concurrently, to avoid hammering the site.
 pub struct Downloader {
     client: Client,
-    session: String,
+    base_url: String,
+    session: Option<String>,
+    cache_dir: PathBuf,
+    refresh: bool,
+    max_in_flight: usize,
+    delay: Duration,
+    last_request: Mutex<Option<Instant>>,
 }
 
 impl Downloader {
-    pub fn new() -> Downloader {
-        let session = env::var("SESSION")
-            .map(|s| {
-                println!("Reading session cookie from SESSION environment variable");
-                s
-            })
-            .or_else::<(env::VarError, io::Error), _>(|e1| {
-                fs::File::open("SESSION")
-                    .and_then(|mut f| {
-                        let mut contents = String::new();
-                        f.read_to_string(&mut contents)?;
-
-                        println!("Reading session cookie from SESSION file");
-                        Ok(contents)
-                    })
-                    .map_err(|e2| (e1, e2))
-            })
-            .expect("SESSION environment variable or file is required (your session cookie)");
+    /// Reads the session cookie from the `SESSION` environment variable, or
+    /// else from a `SESSION` file in the current directory.
+    pub fn new() -> Result<Downloader, DownloadError> {
+        let session = match env::var("SESSION") {
+            Ok(session) => {
+                eprintln!("Reading session cookie from SESSION environment variable");
+                session
+            },
+            Err(_) => match fs::read_to_string("SESSION") {
+                Ok(session) => {
+                    eprintln!("Reading session cookie from SESSION file");
+                    session
+                },
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                    return Err(DownloadError::MissingSession);
+                },
+                Err(e) => return Err(e.into()),
+            },
+        };
+
+        Ok(Downloader::with_session(session.trim()))
+    }
+
+    /// Uses a [`Profile`](struct.Profile.html)'s session, caching inputs in
+    /// its cache directory.
+    pub fn with_profile(profile: &Profile) -> Result<Downloader, DownloadError> {
+        let session = profile.session.as_ref().ok_or(DownloadError::MissingSession)?;
+
+        Ok(Downloader::with_session(session.as_str()).cache_dir(&profile.cache_dir))
+    }
+
+    pub fn with_session<S: Into<String>>(session: S) -> Downloader {
+        Downloader {
+            session: Some(session.into()),
+            ..Downloader::cache_only()
+        }
+    }
 
+    /// A downloader without a session, which can only read cached inputs.
+    pub fn cache_only() -> Downloader {
         Downloader {
             client: Client::new(),
-            session,
+            base_url: "https://adventofcode.com".to_string(),
+            session: None,
+            cache_dir: PathBuf::from("inputs"),
+            refresh: false,
+            max_in_flight: 2,
+            delay: Duration::from_secs(1),
+            last_request: Mutex::new(None),
+        }
+    }
+
+    /// Sets the site to talk to (`https://adventofcode.com` by default).
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Downloader {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Sets the directory inputs are cached in (`inputs` by default).
+    ///
+    /// Uses the same layout as `test_inputs`, which can then be used as a
+    /// cache too.
+    pub fn cache_dir<P: Into<PathBuf>>(mut self, cache_dir: P) -> Downloader {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    /// Ignores cached inputs, downloading (and caching) them again.
+    pub fn refresh(mut self, refresh: bool) -> Downloader {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Sets how many inputs [`inputs`](#method.inputs) downloads at once (2 by
+    /// default).
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Downloader {
+        self.max_in_flight = max_in_flight;
+        self
+    }
+
+    /// Sets the minimum delay between two requests (1 second by default).
+    pub fn delay(mut self, delay: Duration) -> Downloader {
+        self.delay = delay;
+        self
+    }
+
+    fn cache_path(&self, year: usize, day: usize) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("{:02}", day))
+    }
+
+    pub fn input(&self, year: usize, day: usize) -> Result<String, DownloadError> {
+        let path = self.cache_path(year, day);
+
+        if !self.refresh {
+            if let Ok(input) = fs::read_to_string(&path) {
+                return Ok(input);
+            }
+        }
+
+        let input = self.download_input(year, day)?;
+
+        fs::create_dir_all(self.cache_dir.join(year.to_string()))?;
+        fs::write(&path, &input)?;
+
+        Ok(input)
+    }
+
+    /// Gets the inputs for several `(year, day)` pairs concurrently, with at
+    /// most `max_in_flight` downloads at once.
+    ///
+    /// Inputs are sent through the returned channel as soon as they are
+    /// available, so they can be used while the rest are still downloading.
+    pub fn inputs(
+        self: Arc<Self>,
+        days: Vec<(usize, usize)>,
+    ) -> mpsc::Receiver<((usize, usize), Result<String, DownloadError>)> {
+        let (tx, rx) = mpsc::channel();
+
+        let workers = cmp::min(cmp::max(self.max_in_flight, 1), days.len());
+        let queue = Arc::new(Mutex::new(days.into_iter().collect::<VecDeque<_>>()));
+
+        for _ in 0..workers {
+            let downloader = self.clone();
+            let queue = queue.clone();
+            let tx = tx.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let next = queue.lock().unwrap().pop_front();
+
+                    let (year, day) = match next {
+                        Some(next) => next,
+                        None => break,
+                    };
+
+                    if tx.send(((year, day), downloader.input(year, day))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        rx
+    }
+
+    /// Waits until at least `delay` has passed since the last request.
+    fn throttle(&self) {
+        let mut last_request = self.last_request.lock().unwrap();
+
+        if let Some(last_request) = *last_request {
+            let elapsed = last_request.elapsed();
+
+            if elapsed < self.delay {
+                thread::sleep(self.delay - elapsed);
+            }
+        }
+
+        *last_request = Some(Instant::now());
+    }
+
+    fn session(&self) -> Result<String, DownloadError> {
+        self.session.as_ref()
+            .map(|session| format!("session={}", session))
+            .ok_or(DownloadError::MissingSession)
+    }
+
+    fn download_input(&self, year: usize, day: usize) -> Result<String, DownloadError> {
+        let session = self.session()?;
+
+        self.throttle();
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+
+        let mut res = self
+            .client
+            .get(&url)
+            .header("Cookie", session)
+            .send()?;
+
+        let body = res.text()?;
+
+        if res.status().is_success() {
+            Ok(body)
+        } else if res.status() == StatusCode::NOT_FOUND && body.contains("unlocks") {
+            Err(DownloadError::NotUnlocked { year, day })
+        } else {
+            Err(DownloadError::Http { status: res.status(), body })
+        }
+    }
+
+    /// Downloads a day's puzzle description. Without a session only part 1 is
+    /// included, and with one part 2 is too once part 1 is solved.
+    pub fn puzzle(&self, year: usize, day: usize) -> Result<Puzzle, DownloadError> {
+        self.throttle();
+
+        let url = format!("{}/{}/day/{}", self.base_url, year, day);
+
+        let mut req = self.client.get(&url);
+
+        if let Ok(session) = self.session() {
+            req = req.header("Cookie", session);
+        }
+
+        let mut res = req.send()?;
+        let body = res.text()?;
+
+        if res.status() == StatusCode::NOT_FOUND {
+            return Err(DownloadError::NotUnlocked { year, day });
+        }
+
+        if !res.status().is_success() {
+            return Err(DownloadError::Http { status: res.status(), body });
         }
+
+        Puzzle::parse(year, day, &body)
+            .ok_or(DownloadError::UnexpectedResponse(body))
     }
 
-    pub fn input(&self, year: usize, day: usize) -> String {
-        let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
+    /// Gets a private leaderboard, caching it as
+    /// `<cache dir>/<year>/leaderboard_<id>.json`.
+    ///
+    /// The site asks not to request leaderboards more than once every 15
+    /// minutes, so until then the cached one is used (even with
+    /// [`refresh`](#method.refresh)). Without a session, it is used however
+    /// old it is.
+    pub fn leaderboard(&self, year: usize, id: u64) -> Result<Leaderboard, DownloadError> {
+        let path = self.cache_dir
+            .join(year.to_string())
+            .join(format!("leaderboard_{}.json", id));
+
+        let fresh = fs::metadata(&path)
+            .and_then(|x| x.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age < LEADERBOARD_MAX_AGE);
+
+        let json = match fs::read_to_string(&path) {
+            Ok(json) if fresh || self.session.is_none() => json,
+            _ => {
+                let json = self.download_leaderboard(year, id)?;
+
+                fs::create_dir_all(self.cache_dir.join(year.to_string()))?;
+                fs::write(&path, &json)?;
+
+                json
+            },
+        };
+
+        Leaderboard::parse(&json)
+            .ok_or(DownloadError::UnexpectedResponse(json))
+    }
+
+    fn download_leaderboard(&self, year: usize, id: u64) -> Result<String, DownloadError> {
+        let session = self.session()?;
+
+        self.throttle();
+
+        let url = format!("{}/{}/leaderboard/private/view/{}.json", self.base_url, year, id);
 
         let mut res = self
             .client
             .get(&url)
-            .header("Cookie", format!("session={}", self.session))
-            .send()
-            .expect("Error requesting input");
+            .header("Cookie", session)
+            .send()?;
+
+        let body = res.text()?;
+
+        if !res.status().is_success() {
+            return Err(DownloadError::Http { status: res.status(), body });
+        }
+
+        Ok(body)
+    }
+
+    /// Submits the `answer` for a `part` (1 or 2) of a day's puzzle.
+    pub fn submit(
+        &self,
+        year: usize,
+        day: usize,
+        part: usize,
+        answer: &Answer,
+    ) -> Result<Submission, DownloadError> {
+        let session = self.session()?;
+
+        self.throttle();
+
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+
+        let mut res = self
+            .client
+            .post(&url)
+            .header("Cookie", session)
+            .form(&[("level", part.to_string()), ("answer", answer.to_string())])
+            .send()?;
+
+        let body = res.text()?;
+
+        if !res.status().is_success() {
+            return Err(DownloadError::Http { status: res.status(), body });
+        }
+
+        Submission::parse(&body)
+            .ok_or(DownloadError::UnexpectedResponse(body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{ BufRead, BufReader, Read, Write };
+    use std::net::TcpListener;
+    use std::{ process, thread };
+    use std::time::Duration;
+
+    use Answer;
+    use super::*;
+
+    /// Serves a single HTTP response on localhost, returning the base URL to
+    /// point a `Downloader` to and a handle to the received request.
+    fn serve_once(status: &'static str, body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+
+                if line.to_lowercase().starts_with("content-length:") {
+                    content_length = line[15..].trim().parse().unwrap();
+                }
+
+                request.push_str(&line);
+
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body,
+            ).unwrap();
 
-        assert!(
-            res.status().is_success(),
-            "Error requesting input (invalid session cookie?)"
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    fn submit(body: &'static str) -> (Result<Submission, DownloadError>, String) {
+        let (base_url, handle) = serve_once("200 OK", body);
+
+        let result = Downloader::with_session("cookie")
+            .base_url(base_url)
+            .submit(2017, 3, 2, &Answer::from(312453usize));
+
+        (result, handle.join().unwrap())
+    }
+
+    #[test]
+    fn submit_request() {
+        let (_, request) = submit("That's the right answer!");
+
+        assert!(request.starts_with("POST /2017/day/3/answer "));
+        assert!(request.contains("session=cookie"));
+        assert!(request.ends_with("level=2&answer=312453"));
+    }
+
+    #[test]
+    fn submit_correct() {
+        let (result, _) = submit("<p>That's the right answer! You are one gold star closer.</p>");
+
+        assert_eq!(result.unwrap(), Submission::Correct);
+    }
+
+    #[test]
+    fn submit_incorrect() {
+        let (result, _) = submit("<p>That's not the right answer; your answer is too high.</p>");
+        assert_eq!(result.unwrap(), Submission::Incorrect { hint: Some(Hint::TooHigh) });
+
+        let (result, _) = submit("<p>That's not the right answer; your answer is too low.</p>");
+        assert_eq!(result.unwrap(), Submission::Incorrect { hint: Some(Hint::TooLow) });
+
+        let (result, _) = submit("<p>That's not the right answer.</p>");
+        assert_eq!(result.unwrap(), Submission::Incorrect { hint: None });
+    }
+
+    #[test]
+    fn submit_rate_limited() {
+        let (result, _) = submit("<p>You gave an answer too recently. You have 1m 23s left to wait.</p>");
+
+        assert_eq!(result.unwrap(), Submission::RateLimited { wait: Duration::from_secs(83) });
+    }
+
+    #[test]
+    fn submit_already_solved() {
+        let (result, _) = submit("<p>You don't seem to be solving the right level.  Did you already complete it?</p>");
+
+        assert_eq!(result.unwrap(), Submission::AlreadySolved);
+    }
+
+    #[test]
+    fn submit_unexpected() {
+        match submit("<p>Something else</p>").0 {
+            Err(DownloadError::UnexpectedResponse(_)) => {},
+            x => panic!("Unexpected {:?}", x),
+        }
+    }
+
+    #[test]
+    fn puzzle() {
+        let (base_url, handle) = serve_once(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 3: Spiral Memory ---</h2>\
+             <p>You come across an <em>experimental</em> new kind of memory.</p></article></main>",
+        );
+
+        let puzzle = Downloader::cache_only()
+            .base_url(base_url)
+            .puzzle(2017, 3)
+            .unwrap();
+
+        let request = handle.join().unwrap();
+
+        assert!(request.starts_with("GET /2017/day/3 "));
+        assert!(!request.contains("session="));
+        assert_eq!(puzzle.title, "Day 3: Spiral Memory");
+        assert_eq!(puzzle.parts, vec!["You come across an *experimental* new kind of memory.\n"]);
+    }
+
+    #[test]
+    fn leaderboard() {
+        let (base_url, handle) = serve_once(
+            "200 OK",
+            include_str!("../fixtures/leaderboard_2017.json"),
+        );
+
+        let cache_dir = env::temp_dir().join(format!("advent_leaderboard_{}", process::id()));
+
+        let downloader = Downloader::with_session("cookie")
+            .base_url(base_url)
+            .cache_dir(&cache_dir);
+
+        let leaderboard = downloader.leaderboard(2017, 1001).unwrap();
+
+        let request = handle.join().unwrap();
+
+        assert!(request.starts_with("GET /2017/leaderboard/private/view/1001.json "));
+        assert!(request.contains("session=cookie"));
+        assert_eq!(leaderboard.members.len(), 4);
+
+        // The server is gone by now, so this must come from the cache.
+        assert_eq!(downloader.leaderboard(2017, 1001).unwrap(), leaderboard);
+
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+
+    #[test]
+    fn input_not_unlocked() {
+        let (base_url, handle) = serve_once(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint before it unlocks!",
         );
 
-        res.text().expect("Error reading input")
+        let result = Downloader::with_session("cookie")
+            .base_url(base_url)
+            .refresh(true)
+            .input(2017, 26);
+
+        handle.join().unwrap();
+
+        match result {
+            Err(DownloadError::NotUnlocked { year: 2017, day: 26 }) => {},
+            x => panic!("Unexpected {:?}", x),
+        }
     }
 }
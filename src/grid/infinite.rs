@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::{ fmt, ops };
+
+use super::Grid;
+use ::Point2;
+
+/// Where an [`InfiniteGrid`](struct.InfiniteGrid.html) keeps its cells.
+pub trait Backend<T: Default>: Default {
+    fn get(&self, position: (isize, isize)) -> Option<&T>;
+
+    /// The cell at `position`, which starts as the default if it was not
+    /// stored yet.
+    fn get_mut(&mut self, position: (isize, isize)) -> &mut T;
+
+    /// Every stored cell (which may include some defaults), in no particular
+    /// order.
+    fn cells<'a>(&'a self) -> Box<dyn Iterator<Item=((isize, isize), &'a T)> + 'a>;
+}
+
+/// Stores each cell in a hash map, for cells few and far apart.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Hashed<T>(HashMap<(isize, isize), T>);
+
+impl<T> Default for Hashed<T> {
+    fn default() -> Self {
+        Hashed(HashMap::new())
+    }
+}
+
+impl<T: Default> Backend<T> for Hashed<T> {
+    fn get(&self, position: (isize, isize)) -> Option<&T> {
+        self.0.get(&position)
+    }
+
+    fn get_mut(&mut self, position: (isize, isize)) -> &mut T {
+        self.0.entry(position).or_default()
+    }
+
+    fn cells<'a>(&'a self) -> Box<dyn Iterator<Item=((isize, isize), &'a T)> + 'a> {
+        Box::new(self.0.iter().map(|(&position, cell)| (position, cell)))
+    }
+}
+
+/// The side of a chunk.
+const CHUNK: isize = 16;
+
+/// Stores cells in square chunks, allocated as they are first written to and
+/// laid out in a rectangle of chunks which grows to fit. Indexing does not
+/// need to hash, so this suits cells close together (like an automaton's).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Chunked<T> {
+    /// The chunk coordinates of the first chunk.
+    origin: (isize, isize),
+    /// In chunks.
+    width: usize,
+    height: usize,
+    /// Row by row.
+    chunks: Vec<Option<Vec<T>>>,
+}
+
+impl<T> Default for Chunked<T> {
+    fn default() -> Self {
+        Chunked { origin: (0, 0), width: 0, height: 0, chunks: vec![] }
+    }
+}
+
+/// The chunk `position` is in, and its index in that chunk.
+fn chunk_of((x, y): (isize, isize)) -> ((isize, isize), usize) {
+    (
+        (x.div_euclid(CHUNK), y.div_euclid(CHUNK)),
+        (y.rem_euclid(CHUNK) * CHUNK + x.rem_euclid(CHUNK)) as usize,
+    )
+}
+
+impl<T> Chunked<T> {
+    fn index(&self, (x, y): (isize, isize)) -> Option<usize> {
+        let x = x - self.origin.0;
+        let y = y - self.origin.1;
+
+        if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+            Some(y as usize * self.width + x as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Makes room for the chunk at `(x, y)`, at least doubling the rectangle
+    /// in the directions it has to grow so that growing is rare.
+    fn grow(&mut self, (x, y): (isize, isize)) {
+        let (width, height) = (self.width as isize, self.height as isize);
+
+        let (left, top) = if self.chunks.is_empty() { (x, y) } else { self.origin };
+        let (right, bottom) = if self.chunks.is_empty() { (x, y) } else { (left + width - 1, top + height - 1) };
+
+        let left = if x < left { x.min(left - width) } else { left };
+        let top = if y < top { y.min(top - height) } else { top };
+        let right = if x > right { x.max(right + width) } else { right };
+        let bottom = if y > bottom { y.max(bottom + height) } else { bottom };
+
+        let mut grown = Chunked {
+            origin: (left, top),
+            width: (right - left + 1) as usize,
+            height: (bottom - top + 1) as usize,
+            chunks: vec![],
+        };
+
+        grown.chunks.resize_with(grown.width * grown.height, || None);
+
+        for (i, chunk) in self.chunks.drain(..).enumerate() {
+            let old = (self.origin.0 + i as isize % width, self.origin.1 + i as isize / width);
+            let new = grown.index(old).unwrap();
+            grown.chunks[new] = chunk;
+        }
+
+        *self = grown;
+    }
+}
+
+impl<T: Default> Backend<T> for Chunked<T> {
+    fn get(&self, position: (isize, isize)) -> Option<&T> {
+        let (chunk, i) = chunk_of(position);
+
+        self.index(chunk)
+            .and_then(|index| self.chunks[index].as_ref())
+            .map(|chunk| &chunk[i])
+    }
+
+    fn get_mut(&mut self, position: (isize, isize)) -> &mut T {
+        let (chunk, i) = chunk_of(position);
+
+        let index = match self.index(chunk) {
+            Some(index) => index,
+            None => {
+                self.grow(chunk);
+                self.index(chunk).unwrap()
+            },
+        };
+
+        let chunk = self.chunks[index].get_or_insert_with(|| {
+            (0..CHUNK * CHUNK).map(|_| T::default()).collect()
+        });
+
+        &mut chunk[i]
+    }
+
+    fn cells<'a>(&'a self) -> Box<dyn Iterator<Item=((isize, isize), &'a T)> + 'a> {
+        let width = self.width as isize;
+        let origin = self.origin;
+
+        Box::new(self.chunks.iter()
+            .enumerate()
+            .filter_map(|(i, chunk)| chunk.as_ref().map(|chunk| (i as isize, chunk)))
+            .flat_map(move |(i, chunk)| {
+                let left = (origin.0 + i % width) * CHUNK;
+                let top = (origin.1 + i / width) * CHUNK;
+
+                chunk.iter()
+                    .enumerate()
+                    .map(move |(j, cell)| ((left + j as isize % CHUNK, top + j as isize / CHUNK), cell))
+            })
+        )
+    }
+}
+
+/// A grid without bounds, indexed by `(x, y)` in any direction. Cells start
+/// as the default, and only those written to take up room.
+///
+/// ```
+/// # use advent_solutions::grid::{ Hashed, InfiniteGrid };
+/// let mut grid = InfiniteGrid::<u8, Hashed<u8>>::new();
+///
+/// grid[(-3, 2)] = 1;
+/// grid[(5, -1)] += 2;
+///
+/// assert_eq!(grid[(0, 0)], 0);
+/// assert_eq!(grid.bounds(), Some(((-3, -1), (5, 2))));
+/// assert_eq!(grid.iter().count(), 2);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InfiniteGrid<T, B = Chunked<T>> {
+    cells: B,
+    /// What unstored cells are.
+    default: T,
+}
+
+impl<T: Default, B: Backend<T>> Default for InfiniteGrid<T, B> {
+    fn default() -> Self {
+        InfiniteGrid { cells: B::default(), default: T::default() }
+    }
+}
+
+impl<T: Default, B: Backend<T>> InfiniteGrid<T, B> {
+    pub fn new() -> Self {
+        InfiniteGrid::default()
+    }
+
+    /// The cells of `grid`, with its top left corner at `(x, y)`.
+    pub fn from_grid(grid: &Grid<T>, (x, y): (isize, isize)) -> Self
+        where T: Clone,
+    {
+        let mut result = InfiniteGrid::new();
+
+        for (gx, gy) in grid.positions() {
+            result[(x + gx as isize, y + gy as isize)] = grid[(gx, gy)].clone();
+        }
+
+        result
+    }
+}
+
+impl<T: Default + PartialEq, B: Backend<T>> InfiniteGrid<T, B> {
+    /// Every cell which is not the default, in no particular order.
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item=((isize, isize), &'a T)> + 'a {
+        self.cells.cells()
+            .filter(move |&(_, cell)| *cell != self.default)
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle with
+    /// every cell which is not the default, or `None` if there are none.
+    pub fn bounds(&self) -> Option<((isize, isize), (isize, isize))> {
+        self.iter()
+            .map(|(position, _)| position)
+            .fold(None, |bounds, (x, y)| Some(match bounds {
+                None => ((x, y), (x, y)),
+                Some(((left, top), (right, bottom))) => (
+                    (left.min(x), top.min(y)),
+                    (right.max(x), bottom.max(y)),
+                ),
+            }))
+    }
+}
+
+impl<T: Default, B: Backend<T>> ops::Index<(isize, isize)> for InfiniteGrid<T, B> {
+    type Output = T;
+
+    fn index(&self, position: (isize, isize)) -> &T {
+        self.cells.get(position).unwrap_or(&self.default)
+    }
+}
+
+impl<T: Default, B: Backend<T>> ops::IndexMut<(isize, isize)> for InfiniteGrid<T, B> {
+    fn index_mut(&mut self, position: (isize, isize)) -> &mut T {
+        self.cells.get_mut(position)
+    }
+}
+
+impl<T: Default, B: Backend<T>> ops::Index<Point2<isize>> for InfiniteGrid<T, B> {
+    type Output = T;
+
+    fn index(&self, position: Point2<isize>) -> &T {
+        &self[(position.x, position.y)]
+    }
+}
+
+impl<T: Default, B: Backend<T>> ops::IndexMut<Point2<isize>> for InfiniteGrid<T, B> {
+    fn index_mut(&mut self, position: Point2<isize>) -> &mut T {
+        &mut self[(position.x, position.y)]
+    }
+}
+
+/// The rectangle given by [`bounds`](#method.bounds), a line for each row.
+impl<T, B> fmt::Display for InfiniteGrid<T, B>
+    where T: Default + PartialEq + Copy + Into<char>,
+          B: Backend<T>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(((left, top), (right, bottom))) = self.bounds() {
+            for y in top..=bottom {
+                for x in left..=right {
+                    let cell: char = self[(x, y)].into();
+                    write!(f, "{}", cell)?;
+                }
+
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backend<B: Backend<char>>() {
+        let mut grid = InfiniteGrid::<char, B>::new();
+
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        for &(position, c) in &[((0, 0), 'a'), ((-17, 3), 'b'), ((40, -33), 'c'), ((2, 1), 'd')] {
+            grid[position] = c;
+        }
+
+        grid[(2, 1)] = '\0';
+
+        let mut cells = grid.iter().map(|(position, &c)| (position, c)).collect::<Vec<_>>();
+        cells.sort();
+
+        assert_eq!(cells, vec![((-17, 3), 'b'), ((0, 0), 'a'), ((40, -33), 'c')]);
+        assert_eq!(grid.bounds(), Some(((-17, -33), (40, 3))));
+        assert_eq!(grid[(-17, 3)], 'b');
+        assert_eq!(grid[(1000, 1000)], '\0');
+    }
+
+    #[test]
+    fn backends() {
+        backend::<Chunked<char>>();
+        backend::<Hashed<char>>();
+    }
+
+    #[test]
+    fn from_grid() {
+        let grid = "ab\ncd\n".parse::<Grid<char>>().unwrap();
+        let infinite = InfiniteGrid::<char>::from_grid(&grid, (-1, -1));
+
+        assert_eq!(infinite[(0, 0)], 'd');
+        assert_eq!(infinite.to_string(), "ab\ncd\n");
+    }
+}
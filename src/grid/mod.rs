@@ -0,0 +1,414 @@
+//! Grids of cells, like the maps in many puzzle inputs.
+
+use std::convert::TryFrom;
+use std::{ fmt, ops, slice };
+
+use ::Direction;
+use ::Direction::*;
+use ::{ ParseError, Point2 };
+
+mod infinite;
+pub use self::infinite::{ InfiniteGrid, Backend, Chunked, Hashed };
+
+static DIRECTIONS: [Direction; 4] = [Up, Right, Down, Left];
+
+/// A rectangular grid, indexed by `(x, y)` from its top left corner.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    cells: Vec<T>,
+}
+
+/// `position` moved by `offset`, if it is still inside `size`.
+fn offset(
+    (x, y): (usize, usize),
+    (dx, dy): (isize, isize),
+    (width, height): (usize, usize),
+) -> Option<(usize, usize)> {
+    let x = x.checked_add_signed(dx)?;
+    let y = y.checked_add_signed(dy)?;
+
+    if x < width && y < height {
+        Some((x, y))
+    } else {
+        None
+    }
+}
+
+impl<T> Grid<T> {
+    /// A grid with the cell at each position given by `f`.
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Grid<T>
+        where F: FnMut((usize, usize)) -> T,
+    {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+
+        Grid { width, height, cells }
+    }
+
+    /// A grid from its rows, or `None` if they are not all as wide.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Grid<T>> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+
+        Some(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if self.contains((x, y)) {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if self.contains((x, y)) {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item=(usize, usize)> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `x` is not a column of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        assert!(x < self.width, "No column {} in a grid {} wide", x, self.width);
+
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    /// The positions next to `position` (up, right, down and left, as long as
+    /// they are in the grid) and their directions.
+    ///
+    /// ```
+    /// # use advent_solutions::Direction::*;
+    /// # use advent_solutions::grid::Grid;
+    /// let grid = Grid::from_fn(3, 2, |_| 0);
+    ///
+    /// assert_eq!(
+    ///     grid.neighbors4((0, 1)).collect::<Vec<_>>(),
+    ///     vec![(Up, (0, 0)), (Right, (1, 1))]
+    /// );
+    /// ```
+    pub fn neighbors4(&self, position: (usize, usize)) -> impl Iterator<Item=(Direction, (usize, usize))> {
+        let size = (self.width, self.height);
+
+        DIRECTIONS.iter()
+            .filter_map(move |&direction| {
+                offset(position, direction.into(), size).map(|neighbor| (direction, neighbor))
+            })
+    }
+
+    /// The positions around `position` (diagonals included) which are in the
+    /// grid, clockwise from the one above it.
+    ///
+    /// ```
+    /// # use advent_solutions::grid::Grid;
+    /// let grid = Grid::from_fn(3, 2, |_| 0);
+    ///
+    /// assert_eq!(grid.neighbors8((2, 0)).collect::<Vec<_>>(), vec![(2, 1), (1, 1), (1, 0)]);
+    /// ```
+    pub fn neighbors8(&self, position: (usize, usize)) -> impl Iterator<Item=(usize, usize)> {
+        let size = (self.width, self.height);
+
+        DIRECTIONS.iter()
+            .flat_map(|&direction| {
+                let (dx, dy) = direction.into();
+                let (cx, cy) = direction.cw().into();
+
+                [(dx, dy), (dx + cx, dy + cy)]
+            })
+            .filter_map(move |delta| offset(position, delta, size))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+        where F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, cell: T) -> Grid<T> {
+        Grid { width, height, cells: vec![cell; width * height] }
+    }
+
+    /// A copy of the `width` by `height` part of the grid whose top left
+    /// corner is at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if that part does not fit in the grid.
+    pub fn region(&self, (x, y): (usize, usize), (width, height): (usize, usize)) -> Grid<T> {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "{}x{} region at {:?} out of a {}x{} grid",
+            width, height, (x, y), self.width, self.height
+        );
+
+        Grid::from_fn(width, height, |(xx, yy)| self[(x + xx, y + yy)].clone())
+    }
+
+    /// Swaps rows and columns.
+    pub fn transpose(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_x(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |(x, y)| self[(self.width - x - 1, y)].clone())
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_y(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |(x, y)| self[(x, self.height - y - 1)].clone())
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, self.height - x - 1)].clone())
+    }
+
+    /// Rotates the grid a quarter turn counterclockwise.
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(self.width - y - 1, x)].clone())
+    }
+
+    /// The eight ways of rotating and flipping the grid (which may not all be
+    /// different), starting with the grid itself.
+    pub fn flips_and_rotations(&self) -> Vec<Grid<T>> {
+        let mut result = vec![];
+
+        for grid in [self.clone(), self.flip_x()] {
+            let mut rotated = grid;
+
+            for _ in 0..4 {
+                let next = rotated.rotate_cw();
+                result.push(rotated);
+                rotated = next;
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: Default> Grid<T> {
+    /// Like [`parse`](#method.parse), but short rows (e.g. diagrams which
+    /// lost their trailing spaces) are padded with default cells.
+    pub fn parse_padded(input: &str, expected: &str) -> Result<Grid<T>, ParseError>
+        where T: TryFrom<char>,
+    {
+        let mut rows = parse_rows(input, expected)?;
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+
+        for row in &mut rows {
+            row.resize_with(width, T::default);
+        }
+
+        Ok(Grid::from_rows(rows).unwrap())
+    }
+}
+
+impl<T: TryFrom<char>> Grid<T> {
+    /// Parses a line for each row and a character for each cell, pointing at
+    /// the first character which is not a cell (described by `expected`) or
+    /// at a row of a different width than the first.
+    ///
+    /// ```
+    /// # use advent_solutions::grid::Grid;
+    /// let grid = "#..\n.#.\n".parse::<Grid<char>>().unwrap();
+    ///
+    /// assert_eq!((grid.width(), grid.height()), (3, 2));
+    /// assert_eq!(grid[(1, 1)], '#');
+    /// assert_eq!(grid.to_string(), "#..\n.#.\n");
+    ///
+    /// let error = Grid::<char>::parse("#..\n.#\n", "a cell").unwrap_err();
+    ///
+    /// assert_eq!((error.line, error.column), (2, 3));
+    /// ```
+    pub fn parse(input: &str, expected: &str) -> Result<Grid<T>, ParseError> {
+        let rows = parse_rows(input, expected)?;
+        let width = rows.first().map_or(0, Vec::len);
+
+        for (row, line) in rows.iter().zip(input.lines()) {
+            if row.len() != width {
+                let (end, expected) = match line.char_indices().nth(width) {
+                    Some((i, _)) => (i, "the end of the row"),
+                    None => (line.len(), expected),
+                };
+
+                return Err(ParseError::at_token(input, &line[end..], expected));
+            }
+        }
+
+        Ok(Grid::from_rows(rows).unwrap())
+    }
+}
+
+fn parse_rows<T: TryFrom<char>>(input: &str, expected: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    input.lines()
+        .map(|line| line.char_indices()
+            .map(|(i, c)| T::try_from(c)
+                .map_err(|_| ParseError::at_token(input, &line[i..], expected))
+            )
+            .collect()
+        )
+        .collect()
+}
+
+impl<T: TryFrom<char>> ::std::str::FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Grid<T>, ParseError> {
+        Grid::parse(input, "a cell")
+    }
+}
+
+impl<T> ops::Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: (usize, usize)) -> &T {
+        let (width, height) = (self.width, self.height);
+
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} out of a {}x{} grid", position, width, height))
+    }
+}
+
+impl<T> ops::IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} out of a {}x{} grid", position, width, height))
+    }
+}
+
+impl<T> ops::Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point2<usize>) -> &T {
+        &self[(position.x, position.y)]
+    }
+}
+
+impl<T> ops::IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: Point2<usize>) -> &mut T {
+        &mut self[(position.x, position.y)]
+    }
+}
+
+/// A line for each row.
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for &cell in row {
+                let cell: char = cell.into();
+                write!(f, "{}", cell)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(input: &str) -> Grid<char> {
+        input.parse().unwrap()
+    }
+
+    #[test]
+    fn views() {
+        let grid = grid("abc\ndef\nghi\n");
+
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(2).collect::<String>(), "cfi");
+        assert_eq!(grid.region((1, 1), (2, 2)).to_string(), "ef\nhi\n");
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.neighbors8((1, 1)).map(|p| grid[p]).collect::<String>(), "bcfihgda");
+    }
+
+    #[test]
+    fn transforms() {
+        let grid = grid("ab\ncd\nef\n");
+
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.flip_x().to_string(), "ba\ndc\nfe\n");
+        assert_eq!(grid.flip_y().to_string(), "ef\ncd\nab\n");
+        assert_eq!(grid.rotate_cw().to_string(), "eca\nfdb\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "bdf\nace\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+
+        let all = grid.flips_and_rotations();
+
+        assert_eq!(all.len(), 8);
+        assert!(all.contains(&grid.transpose()));
+        assert!(all.contains(&grid.flip_y()));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = Grid::<char>::parse("ab\nabc\n", "a cell").unwrap_err();
+        assert_eq!((error.line, error.column, error.expected.as_str()), (2, 3, "the end of the row"));
+
+        let padded = Grid::<char>::parse_padded("ab\na\n", "a cell").unwrap();
+        assert_eq!(padded.row(1), &['a', '\0']);
+    }
+}
@@ -0,0 +1,206 @@
+//! Private leaderboards, as served by
+//! `https://adventofcode.com/YYYY/leaderboard/private/view/ID.json`.
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use serde::de::{ self, Deserialize, Deserializer };
+use serde_json;
+
+/// Seconds since the Unix epoch.
+pub type Timestamp = u64;
+
+/// When a member got each star of a day.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Completion {
+    pub part1: Timestamp,
+    pub part2: Option<Timestamp>,
+}
+
+impl Completion {
+    /// How long part 2 took after part 1, in seconds.
+    pub fn delta(&self) -> Option<u64> {
+        self.part2.map(|part2| part2.saturating_sub(self.part1))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Member {
+    pub id: u64,
+    /// Members can be anonymous.
+    pub name: Option<String>,
+    pub stars: usize,
+    pub local_score: usize,
+    pub global_score: usize,
+    /// When the last star was got, if any.
+    pub last_star: Option<Timestamp>,
+    /// What was completed of each day (1 to 25).
+    pub days: BTreeMap<usize, Completion>,
+}
+
+impl Member {
+    /// The name, or how the site shows anonymous members.
+    pub fn display_name(&self) -> String {
+        self.name.clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Leaderboard {
+    pub year: usize,
+    pub owner_id: u64,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Option<Leaderboard> {
+        let raw = serde_json::from_str::<RawLeaderboard>(json).ok()?;
+
+        let members = raw.members.into_values()
+            .map(|member| {
+                let days = member.completion_day_level.into_iter()
+                    .filter_map(|(day, parts)| {
+                        let part1 = parts.get("1")?.get_star_ts;
+                        let part2 = parts.get("2").map(|x| x.get_star_ts);
+
+                        Some((day.parse::<usize>().ok()?, Completion { part1, part2 }))
+                    })
+                    .collect();
+
+                Member {
+                    id: member.id,
+                    name: member.name,
+                    stars: member.stars,
+                    local_score: member.local_score,
+                    global_score: member.global_score,
+                    last_star: Some(member.last_star_ts).filter(|&x| x > 0),
+                    days,
+                }
+            })
+            .collect();
+
+        Some(Leaderboard {
+            year: raw.event.parse().ok()?,
+            owner_id: raw.owner_id,
+            members,
+        })
+    }
+
+    /// Members by local score, like the site ranks them: ties go to whoever
+    /// got their last star first.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.iter().collect::<Vec<_>>();
+
+        members.sort_by_key(|member| {
+            (Reverse(member.local_score), member.last_star.unwrap_or(Timestamp::MAX), member.id)
+        });
+
+        members
+    }
+
+    /// The days anyone has completed at least part 1 of.
+    pub fn days(&self) -> Vec<usize> {
+        let mut days = self.members.iter()
+            .flat_map(|member| member.days.keys().cloned())
+            .collect::<Vec<_>>();
+
+        days.sort();
+        days.dedup();
+        days
+    }
+}
+
+#[derive(Deserialize)]
+struct RawLeaderboard {
+    event: String,
+    #[serde(deserialize_with = "number")]
+    owner_id: u64,
+    members: BTreeMap<String, RawMember>,
+}
+
+#[derive(Deserialize)]
+struct RawMember {
+    #[serde(deserialize_with = "number")]
+    id: u64,
+    name: Option<String>,
+    stars: usize,
+    local_score: usize,
+    #[serde(default)]
+    global_score: usize,
+    #[serde(deserialize_with = "number")]
+    last_star_ts: Timestamp,
+    completion_day_level: BTreeMap<String, BTreeMap<String, RawStar>>,
+}
+
+#[derive(Deserialize)]
+struct RawStar {
+    #[serde(deserialize_with = "number")]
+    get_star_ts: Timestamp,
+}
+
+/// Older leaderboards have some numbers (like timestamps) as strings.
+fn number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrString {
+        Number(u64),
+        String(String),
+    }
+
+    match NumberOrString::deserialize(deserializer)? {
+        NumberOrString::Number(x) => Ok(x),
+        NumberOrString::String(x) => x.parse().map_err(de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../fixtures/leaderboard_2017.json");
+
+    #[test]
+    fn parse() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+
+        assert_eq!(leaderboard.year, 2017);
+        assert_eq!(leaderboard.owner_id, 1001);
+        assert_eq!(leaderboard.members.len(), 4);
+        assert_eq!(leaderboard.days(), vec![1, 2]);
+
+        let alice = &leaderboard.members[0];
+
+        assert_eq!(alice.display_name(), "Alice");
+        assert_eq!(alice.stars, 4);
+        assert_eq!(alice.days[&1], Completion { part1: 1512104700, part2: Some(1512104820) });
+        assert_eq!(alice.days[&2].delta(), Some(900));
+
+        // Timestamps as strings.
+        let anonymous = &leaderboard.members[1];
+
+        assert_eq!(anonymous.display_name(), "(anonymous user #1002)");
+        assert_eq!(anonymous.last_star, Some(1512191000));
+        assert_eq!(anonymous.days[&2], Completion { part1: 1512191000, part2: None });
+
+        assert_eq!(leaderboard.members[3].last_star, None);
+
+        assert_eq!(Leaderboard::parse("{\"event\": \"2017\"}"), None);
+    }
+
+    #[test]
+    fn ranking() {
+        let mut leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+
+        let ids = |leaderboard: &Leaderboard| {
+            leaderboard.ranked().iter().map(|x| x.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(&leaderboard), vec![1001, 1002, 1003, 1004]);
+
+        // On a tie, the earliest last star wins.
+        leaderboard.members[1].local_score = 11;
+
+        assert_eq!(ids(&leaderboard), vec![1002, 1001, 1003, 1004]);
+    }
+}
@@ -4,41 +4,70 @@
 
 extern crate itertools;
 extern crate reqwest;
+extern crate serde;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
 #[macro_use]
 extern crate nom;
 
 mod download;
-pub use download::Downloader;
+pub use download::{ Downloader, DownloadError, Submission, Hint };
+
+mod config;
+pub use config::{ Config, ConfigError, Profile };
+
+mod leaderboard;
+pub use leaderboard::{ Leaderboard, Member, Completion, Timestamp };
+
+mod puzzle;
+pub use puzzle::{ Puzzle, doc_comment };
+
+mod answer;
+pub use answer::Answer;
+
+mod verify;
+pub use verify::{ Answers, Verdict };
+
+mod solution;
+pub use solution::{ Solution, Day, Parsed, registry };
 
 #[macro_use]
 pub mod parse;
+pub use parse::ParseError;
 
 pub mod iter;
 
+pub mod grid;
+
 mod direction;
 pub use direction::Direction;
 
+mod point;
+pub use point::{ Point2, Point3 };
+
 macro_rules! test_day {
-    ($day:expr, $part1:expr, $part2:expr) => {
+    ($year:expr, $day:expr, $part1:expr, $part2:expr) => {
         #[cfg(test)]
         mod tests {
             #[test]
             fn parse() {
-                super::parse_input(include_str!(concat!("../../test_inputs/2017/", $day)));
+                let input = ::parse::normalize(include_str!(concat!("../../test_inputs/", $year, "/", $day)));
+                super::parse_input(&input).unwrap();
             }
 
             #[test]
             fn part1() {
-                let input =
-                    super::parse_input(include_str!(concat!("../../test_inputs/2017/", $day)));
+                let input = ::parse::normalize(include_str!(concat!("../../test_inputs/", $year, "/", $day)));
+                let input = super::parse_input(&input).unwrap();
 
                 assert_eq!(super::part1(&input), $part1);
             }
 
             #[test]
             fn part2() {
-                let input =
-                    super::parse_input(include_str!(concat!("../../test_inputs/2017/", $day)));
+                let input = ::parse::normalize(include_str!(concat!("../../test_inputs/", $year, "/", $day)));
+                let input = super::parse_input(&input).unwrap();
 
                 assert_eq!(super::part2(&input), $part2);
             }
@@ -47,18 +76,19 @@ macro_rules! test_day {
 }
 
 macro_rules! test_day_both {
-    ($day:expr, $part1:expr, $part2:expr) => {
+    ($year:expr, $day:expr, $part1:expr, $part2:expr) => {
         #[cfg(test)]
         mod tests {
             #[test]
             fn parse() {
-                super::parse_input(include_str!(concat!("../../test_inputs/2017/", $day)));
+                let input = ::parse::normalize(include_str!(concat!("../../test_inputs/", $year, "/", $day)));
+                super::parse_input(&input).unwrap();
             }
 
             #[test]
             fn solve() {
-                let input =
-                    super::parse_input(include_str!(concat!("../../test_inputs/2017/", $day)));
+                let input = ::parse::normalize(include_str!(concat!("../../test_inputs/", $year, "/", $day)));
+                let input = super::parse_input(&input).unwrap();
                 let (part1, part2) = super::solve(&input);
 
                 assert_eq!(part1, $part1);
@@ -68,4 +98,158 @@ macro_rules! test_day_both {
     };
 }
 
-pub mod advent2017;
+/// Declares a day's examples as `(input, part, expected)` rows, with `part`
+/// being `1` or `2` (checked through the day's `Solver`) or a function of the
+/// parsed input (for examples that need other parameters than the puzzle).
+/// They are run as the `examples::part1`, `examples::part2` and
+/// `examples::other` tests.
+///
+/// Parts are checked as an [`Answer`](struct.Answer.html) made from
+/// `expected`, so integers need the type the part returns (e.g. `3usize`).
+macro_rules! test_examples {
+    (@rows [$($part1:tt)*] [$($part2:tt)*] [$($other:tt)*]) => {
+        #[cfg(test)]
+        mod examples {
+            #[allow(unused_imports)]
+            use super::*;
+
+            test_examples!(@test part1 [$($part1)*]);
+            test_examples!(@test part2 [$($part2)*]);
+            test_examples!(@test other [$($other)*]);
+        }
+    };
+
+    (@rows [$($part1:tt)*] [$($part2:tt)*] [$($other:tt)*]
+     ($input:expr, 1, $expected:expr) $($rest:tt)*) => {
+        test_examples!(@rows [$($part1)* ($input, 1, $expected)] [$($part2)*] [$($other)*]
+                       $($rest)*);
+    };
+
+    (@rows [$($part1:tt)*] [$($part2:tt)*] [$($other:tt)*]
+     ($input:expr, 2, $expected:expr) $($rest:tt)*) => {
+        test_examples!(@rows [$($part1)*] [$($part2)* ($input, 2, $expected)] [$($other)*]
+                       $($rest)*);
+    };
+
+    (@rows [$($part1:tt)*] [$($part2:tt)*] [$($other:tt)*]
+     ($input:expr, $f:expr, $expected:expr) $($rest:tt)*) => {
+        test_examples!(@rows [$($part1)*] [$($part2)*] [$($other)* ($input, $f, $expected)]
+                       $($rest)*);
+    };
+
+    (@test $name:ident []) => {};
+
+    (@test part1 [$(($input:expr, 1, $expected:expr))+]) => {
+        #[test]
+        fn part1() {
+            use ::Solution;
+
+            $(
+                let input = super::Solver::parse_input($input).unwrap();
+                assert_eq!(super::Solver::part1(&input), ::Answer::from($expected), "for input {:?}", $input);
+            )+
+        }
+    };
+
+    (@test part2 [$(($input:expr, 2, $expected:expr))+]) => {
+        #[test]
+        fn part2() {
+            use ::Solution;
+
+            $(
+                let input = super::Solver::parse_input($input).unwrap();
+                assert_eq!(super::Solver::part2(&input), ::Answer::from($expected), "for input {:?}", $input);
+            )+
+        }
+    };
+
+    (@test other [$(($input:expr, $f:expr, $expected:expr))+]) => {
+        #[test]
+        fn other() {
+            fn apply<I, T, F: Fn(&I) -> T>(input: &I, f: F) -> T {
+                f(input)
+            }
+
+            $(
+                assert_eq!(apply(&super::parse_input($input).unwrap(), $f), $expected, "for input {:?}", $input);
+            )+
+        }
+    };
+
+    ($($row:tt),* $(,)*) => {
+        test_examples!(@rows [] [] [] $($row)*);
+    };
+}
+
+macro_rules! solution {
+    ($input:ty) => {
+        /// This day's [`Solution`](../../trait.Solution.html).
+        pub struct Solver;
+
+        impl<'a> ::Solution<'a> for Solver {
+            type Input = $input;
+
+            fn parse_input(input: &'a str) -> Result<Self::Input, ::ParseError> {
+                parse_input(input)
+            }
+
+            fn part1(input: &Self::Input) -> ::Answer {
+                part1(input).into()
+            }
+
+            fn part2(input: &Self::Input) -> ::Answer {
+                part2(input).into()
+            }
+        }
+    };
+}
+
+/// Like `solution!`, for days whose `solve` finds both answers in one pass.
+/// Each part on its own still has to run the whole of `solve`, so the runner
+/// calls `solve` once and reports its time for both parts.
+macro_rules! solution_both {
+    ($input:ty) => {
+        /// This day's [`Solution`](../../trait.Solution.html).
+        pub struct Solver;
+
+        impl<'a> ::Solution<'a> for Solver {
+            type Input = $input;
+
+            const SOLVES_BOTH: bool = true;
+
+            fn parse_input(input: &'a str) -> Result<Self::Input, ::ParseError> {
+                parse_input(input)
+            }
+
+            fn part1(input: &Self::Input) -> ::Answer {
+                solve(input).0.into()
+            }
+
+            fn part2(input: &Self::Input) -> ::Answer {
+                solve(input).1.into()
+            }
+
+            fn solve(input: &Self::Input) -> (::Answer, ::Answer) {
+                let (part1, part2) = solve(input);
+
+                (part1.into(), part2.into())
+            }
+        }
+    };
+}
+
+/// Declares every `adventYYYY` module, each of which lists its solved days in
+/// a `days()` function.
+macro_rules! years {
+    ($($year:ident),*) => {
+        $(pub mod $year;)*
+
+        fn days() -> Vec<Day> {
+            let mut days = vec![];
+            $(days.extend($year::days());)*
+            days
+        }
+    };
+}
+
+years!(advent2017);
@@ -1,125 +1,463 @@
 extern crate threadpool;
+extern crate itertools;
 extern crate advent_solutions;
 
-use advent_solutions::advent2017::*;
+mod cli;
+mod report;
+mod bench;
+mod scaffold;
+mod watch;
+mod standings;
 
-macro_rules! run_day {
-    ($day:ident, $args:expr, $downloader:expr, $pool:expr, $tx:expr) => (
-        {
-            let day_string = stringify!($day);
+use std::any::Any;
+use std::collections::{ BTreeMap, HashMap, HashSet };
+use std::io::{ self, Read };
+use std::path::PathBuf;
+use std::panic::{ self, AssertUnwindSafe };
+use std::sync::mpsc::{ self, RecvTimeoutError };
+use std::sync::Arc;
+use std::time::{ Duration, Instant };
+use std::str::FromStr;
+use std::{ fmt, fs, process };
 
-            if $args.contains(&day_string) {
-                let day_num_str = &day_string[day_string.len() - 2..];
-                let day_num = day_num_str
-                    .parse::<usize>()
-                    .expect("Could not parse day number");
+use advent_solutions::{ parse, Answers, Config, Day, Downloader, DownloadError, Profile };
+use cli::{ Command, Format, InputOptions, RunOptions, Selection };
+use report::{ Failure, StepResult };
 
-                let tx = $tx.clone();
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+enum Step { Input, Parse, Part1, Part2 }
 
-                let input = $downloader.input(2017, day_num);
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Step::Input => write!(f, "input"),
+            Step::Parse => write!(f, "parse"),
+            Step::Part1 => write!(f, "1"),
+            Step::Part2 => write!(f, "2"),
+        }
+    }
+}
 
-                $pool.execute(move || {
-                    let parsed = $day::parse_input(&input);
+impl FromStr for Step {
+    type Err = ();
 
-                    tx.send((day_num_str, 1, format!("{}", $day::part1(&parsed))))
-                        .unwrap();
-                    tx.send((day_num_str, 2, format!("{}", $day::part2(&parsed))))
-                        .unwrap();
-                });
-            }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "input" => Ok(Step::Input),
+            "parse" => Ok(Step::Parse),
+            "1" => Ok(Step::Part1),
+            "2" => Ok(Step::Part2),
+            _ => Err(()),
         }
-    )
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    payload.downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<Any>".to_string())
 }
 
-macro_rules! run_day_both {
-    ($day:ident, $args:expr, $downloader:expr, $pool:expr, $tx:expr) => (
-        {
-            let day_string = stringify!($day);
+fn catch<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(panic_message)
+}
 
-            if $args.contains(&day_string) {
-                let day_num_str = &day_string[day_string.len() - 2..];
-                let day_num = day_num_str
-                    .parse::<usize>()
-                    .expect("Could not parse day number");
+fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
 
-                let tx = $tx.clone();
+    (result, start.elapsed())
+}
 
-                let input = $downloader.input(2017, day_num);
+fn select_days(selection: &Selection) -> Result<BTreeMap<(usize, usize), Day>, String> {
+    let registry = advent_solutions::registry();
 
-                $pool.execute(move || {
-                    let parsed = $day::parse_input(&input);
-                    let (part1, part2) = $day::solve(&parsed);
+    if let Some(ref years) = selection.years {
+        for &year in years {
+            if !registry.keys().any(|&(y, _)| y == year) {
+                return Err(format!("No solutions for year {}", year));
+            }
+        }
+    }
 
-                    tx.send((day_num_str, 1, format!("{}", part1)))
-                        .unwrap();
-                    tx.send((day_num_str, 2, format!("{}", part2)))
-                        .unwrap();
-                });
+    let days = registry.into_iter()
+        .filter(|&((year, _), _)| selection.years.as_ref().is_none_or(|x| x.contains(&year)))
+        .collect::<BTreeMap<_, _>>();
+
+    if let Some(ref selected_days) = selection.days {
+        for &day in selected_days {
+            if !days.keys().any(|&(_, d)| d == day) {
+                return Err(format!("No solution for day {}", day));
             }
         }
-    )
+    }
+
+    Ok(days.into_iter()
+        .filter(|&((_, day), _)| selection.days.as_ref().is_none_or(|x| x.contains(&day)))
+        .collect())
 }
 
-fn main() {
-    let str_args = std::env::args()
-        .collect::<Vec<_>>();
+/// The profile selected with `--profile`, if any.
+fn profile(options: &InputOptions) -> Result<Option<Profile>, String> {
+    match options.profile {
+        Some(ref name) => Config::load()
+            .and_then(|config| config.profile(name).cloned())
+            .map(Some)
+            .map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+/// Where inputs are cached: `--cache`, or else the profile's or `inputs`.
+fn cache_dir(options: &InputOptions, profile: Option<&Profile>) -> PathBuf {
+    match (&options.cache_dir, profile) {
+        (Some(cache_dir), _) => PathBuf::from(cache_dir),
+        (None, Some(profile)) => profile.cache_dir.clone(),
+        (None, None) => PathBuf::from("inputs"),
+    }
+}
+
+type Inputs = mpsc::Receiver<((usize, usize), Result<String, DownloadError>)>;
+
+/// Reads the `--input` file, or else downloads inputs for every day (with
+/// the selected `profile`).
+fn get_inputs(
+    options: &InputOptions,
+    profile: Option<&Profile>,
+    days: Vec<(usize, usize)>,
+) -> Result<Inputs, String> {
+    if let Some(ref path) = options.input {
+        if days.len() != 1 {
+            return Err("--input requires selecting a single day".to_string());
+        }
+
+        let (tx, rx) = mpsc::channel();
 
-    let args = if str_args.len() > 1 {
-        str_args.iter()
-            .map(|x| x.as_str())
-            .collect::<Vec<_>>()
-    } else {
-        vec![
-            "day01", "day02", "day03", "day04", "day05",
-            "day06", "day07", "day08", "day09", "day10",
-            "day11", "day12", "day13", "day14", "day15",
-            "day16", "day17", "day18", "day19", "day20",
-            "day21", "day22", "day23", "day24", "day25",
-        ]
+        let input = if path == "-" {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map(|_| input)
+        } else {
+            fs::read_to_string(path)
+        };
+
+        tx.send((days[0], input.map_err(DownloadError::from))).unwrap();
+
+        return Ok(rx);
+    }
+
+    let downloader = match profile {
+        Some(profile) => Downloader::with_profile(profile),
+        None => Downloader::new(),
     };
 
-    let downloader = advent_solutions::Downloader::new();
-
-    let pool = threadpool::Builder::new().build();
-    let (tx, rx) = ::std::sync::mpsc::channel();
-
-    run_day!(day01, args, downloader, pool, tx);
-    run_day!(day02, args, downloader, pool, tx);
-    run_day!(day03, args, downloader, pool, tx);
-    run_day!(day04, args, downloader, pool, tx);
-    run_day!(day05, args, downloader, pool, tx);
-    run_day!(day06, args, downloader, pool, tx);
-    run_day!(day07, args, downloader, pool, tx);
-    run_day!(day08, args, downloader, pool, tx);
-    run_day!(day09, args, downloader, pool, tx);
-    run_day!(day10, args, downloader, pool, tx);
-    run_day_both!(day11, args, downloader, pool, tx);
-    run_day!(day12, args, downloader, pool, tx);
-    run_day!(day13, args, downloader, pool, tx);
-    run_day!(day14, args, downloader, pool, tx);
-    run_day!(day15, args, downloader, pool, tx);
-    run_day!(day16, args, downloader, pool, tx);
-    run_day!(day17, args, downloader, pool, tx);
-    run_day!(day18, args, downloader, pool, tx);
-    run_day_both!(day19, args, downloader, pool, tx);
-    run_day!(day20, args, downloader, pool, tx);
-    run_day!(day21, args, downloader, pool, tx);
-    run_day!(day22, args, downloader, pool, tx);
-    run_day!(day23, args, downloader, pool, tx);
-    run_day!(day24, args, downloader, pool, tx);
-    run_day!(day25, args, downloader, pool, tx);
+    let mut downloader = downloader
+        .unwrap_or_else(|e| {
+            eprintln!("{} (only cached inputs are available)", e);
+            Downloader::cache_only()
+        })
+        .cache_dir(cache_dir(options, profile))
+        .refresh(options.refresh);
 
-    drop(tx);
+    if let Some(downloads) = options.downloads {
+        downloader = downloader.max_in_flight(downloads);
+    }
+
+    if let Some(delay) = options.delay {
+        downloader = downloader.delay(delay);
+    }
+
+    Ok(Arc::new(downloader).inputs(days))
+}
 
+/// What the jobs send back to `run`.
+enum Event {
+    /// A step started running, so its timeout starts counting.
+    Started((usize, usize), Step, Instant),
+    Finished(StepResult),
+}
+
+/// Collects the results of every step, giving up on those that take longer
+/// than `timeout`.
+///
+/// `pending` is how many steps each day has yet to report: its parsing at
+/// first, and then its parts. A parse which times out may still finish and
+/// run its parts later, but by then its day has been given up on.
+fn collect(
+    events: mpsc::Receiver<Event>,
+    mut pending: HashMap<(usize, usize), usize>,
+    parts: usize,
+    timeout: Option<Duration>,
+    pool: &mut threadpool::ThreadPool,
+) -> Vec<StepResult> {
     let mut results = vec![];
+    let mut running = HashMap::new();
+    let mut given_up = HashSet::new();
+
+    while pending.values().any(|&x| x > 0) {
+        let deadline = timeout
+            .and_then(|timeout| running.values().min().map(|&start: &Instant| start + timeout));
+
+        let event = match deadline {
+            Some(deadline) => events.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+            None => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
 
-    for result in rx.iter().take(args.len() * 2) {
-        results.push(result);
+        match event {
+            Ok(Event::Started(key, step, start)) => {
+                if !given_up.contains(&key) {
+                    running.insert((key, step), start);
+                }
+            },
+            Ok(Event::Finished(result)) => {
+                let (key, step, ref answer, _) = result;
+
+                // Already reported as timed out.
+                if running.remove(&(key, step)).is_none() {
+                    continue;
+                }
+
+                let pending = pending.get_mut(&key).unwrap();
+                *pending -= 1;
+
+                if step == Step::Parse && answer.is_ok() {
+                    *pending += parts;
+                }
+
+                results.push(result);
+            },
+            Err(RecvTimeoutError::Timeout) => {
+                let timeout = timeout.unwrap();
+
+                let expired = running.iter()
+                    .filter(|&(_, start)| start.elapsed() >= timeout)
+                    .map(|(&key, _)| key)
+                    .collect::<Vec<_>>();
+
+                for (key, step) in expired {
+                    running.remove(&(key, step));
+                    *pending.get_mut(&key).unwrap() -= 1;
+
+                    if step == Step::Parse {
+                        given_up.insert(key);
+                    }
+
+                    results.push((key, step, Err(Failure::Timeout(timeout)), timeout));
+
+                    // Threads cannot be killed, so the stuck job keeps its
+                    // thread until the process exits. Make up for it.
+                    let threads = pool.max_count() + 1;
+                    pool.set_num_threads(threads);
+                }
+            },
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
     }
 
-    results.sort();
+    results
+}
+
+fn run(options: RunOptions) -> Result<bool, String> {
+    let days = select_days(&options.selection)?;
+    // Before running anything, so that a bad profile loses no results.
+    let profile = profile(&options.inputs)?;
+    let inputs = get_inputs(&options.inputs, profile.as_ref(), days.keys().cloned().collect())?;
+
+    let steps = [Step::Part1, Step::Part2].iter()
+        .cloned()
+        .filter(|&step| options.selection.includes_part(step as usize - 1))
+        .collect::<Vec<_>>();
+
+    let mut pool = threadpool::Builder::new().build();
+    let (tx, rx) = mpsc::channel();
+
+    let mut results = vec![];
+    let mut pending = HashMap::new();
+
+    for (key, input) in inputs {
+        let day = days[&key];
+        let tx = tx.clone();
+        let pool = pool.clone();
+        let steps = steps.clone();
+
+        // Parsed inputs may borrow from the input, which must then outlive
+        // the jobs.
+        let input: &'static str = match input {
+            Ok(input) => Box::leak(parse::normalize(&input).into_boxed_str()),
+            Err(e) => {
+                results.push((key, Step::Input, Err(Failure::Error(e.to_string())), Duration::default()));
+                continue;
+            },
+        };
+
+        pending.insert(key, 1);
+
+        // Sending fails if `collect` has given up, which is fine.
+        pool.clone().execute(move || {
+            let _ = tx.send(Event::Started(key, Step::Parse, Instant::now()));
+            let (parsed, elapsed) = timed(|| catch(|| day.parse_input(input)));
+            let parsed = parsed.and_then(|parsed| parsed.map_err(|e| e.to_string()));
+
+            let parsed = match parsed {
+                Ok(parsed) => {
+                    let _ = tx.send(Event::Finished((key, Step::Parse, Ok(None), elapsed)));
+                    Arc::new(parsed)
+                },
+                Err(e) => {
+                    let _ = tx.send(Event::Finished((key, Step::Parse, Err(Failure::Error(e)), elapsed)));
+                    return;
+                },
+            };
+
+            // Both parts of such a day come out of the same run, which both
+            // report as their time.
+            if day.solves_both() && steps.len() == 2 {
+                pool.execute(move || {
+                    let start = Instant::now();
+
+                    for &step in &steps {
+                        let _ = tx.send(Event::Started(key, step, start));
+                    }
+
+                    let (answers, elapsed) = timed(|| catch(|| day.solve(&parsed)));
+
+                    for step in steps {
+                        let answer = answers.as_ref()
+                            .map(|(part1, part2)| Some(if step == Step::Part1 { part1 } else { part2 }.clone()))
+                            .map_err(|e| Failure::Error(e.clone()));
+
+                        let _ = tx.send(Event::Finished((key, step, answer, elapsed)));
+                    }
+                });
+
+                return;
+            }
+
+            for step in steps {
+                let tx = tx.clone();
+                let parsed = parsed.clone();
+
+                pool.execute(move || {
+                    let _ = tx.send(Event::Started(key, step, Instant::now()));
+
+                    let (answer, elapsed) = timed(|| catch(|| match step {
+                        Step::Part1 => day.part1(&parsed),
+                        _ => day.part2(&parsed),
+                    }));
+
+                    let answer = answer.map(Some).map_err(Failure::Error);
+                    let _ = tx.send(Event::Finished((key, step, answer, elapsed)));
+                });
+            }
+        });
+    }
+
+    drop(tx);
+
+    results.extend(collect(rx, pending, steps.len(), options.timeout, &mut pool));
+
+    let answers_dir = match (options.answers_dir, profile) {
+        (Some(answers_dir), _) => PathBuf::from(answers_dir),
+        (None, Some(profile)) => profile.answers_dir,
+        (None, None) => PathBuf::from("answers"),
+    };
+
+    let mut answers = HashMap::new();
+
+    for &(year, _) in days.keys() {
+        answers.entry(year).or_insert_with(|| {
+            Answers::load(&answers_dir, year)
+                .unwrap_or_else(|e| {
+                    eprintln!("Error reading answers: {}", e);
+                    Answers::default()
+                })
+        });
+    }
+
+    let parts = steps.iter()
+        .map(|&step| step as usize - 1)
+        .collect::<Vec<_>>();
+
+    let records = report::records(results, &parts, &answers);
+
+    match options.format {
+        Format::Text => report::print_text(&records),
+        Format::Json => report::print_json(&records),
+        Format::Csv => report::print_csv(&records),
+    }
+
+    Ok(!records.iter().any(|record| record.failed()))
+}
+
+fn main() {
+    let command = cli::parse(std::env::args().skip(1));
+
+    let result = match command {
+        Ok(Command::Run(options)) => run(options),
+        Ok(Command::Bench(options)) => bench::run(options),
+        Ok(Command::Watch(options)) => watch::run(options),
+        Ok(Command::New(options)) => scaffold::run(options),
+        Ok(Command::Puzzle(options)) => scaffold::print_docs(options),
+        Ok(Command::Leaderboard(options)) => standings::run(options),
+        Ok(Command::Help) => {
+            print!("{}", cli::USAGE);
+            return;
+        },
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(true) => {},
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            eprintln!("Run with --help for usage.");
+            process::exit(2);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn late_parse() {
+        let (tx, rx) = mpsc::channel();
+        let timeout = Duration::from_millis(10);
+        let (slow, other) = ((2017, 1), (2017, 2));
+
+        let mut pending = HashMap::new();
+        pending.insert(slow, 1);
+        pending.insert(other, 1);
+
+        // The slow parse finishes after timing out and runs its parts
+        // anyway, while another day is still pending.
+        let jobs = thread::spawn(move || {
+            tx.send(Event::Started(slow, Step::Parse, Instant::now())).unwrap();
+            thread::sleep(timeout * 5);
+
+            tx.send(Event::Finished((slow, Step::Parse, Ok(None), timeout * 5))).unwrap();
+
+            for &step in &[Step::Part1, Step::Part2] {
+                tx.send(Event::Started(slow, step, Instant::now())).unwrap();
+                tx.send(Event::Finished((slow, step, Ok(None), Duration::default()))).unwrap();
+            }
+
+            tx.send(Event::Started(other, Step::Parse, Instant::now())).unwrap();
+            tx.send(Event::Finished((other, Step::Parse, Err(Failure::Error("boom".to_string())), Duration::default()))).unwrap();
+        });
+
+        let mut pool = threadpool::ThreadPool::new(1);
+        let mut results = collect(rx, pending, 2, Some(timeout), &mut pool);
+        jobs.join().unwrap();
+
+        results.sort_by_key(|&(key, step, ..)| (key, step));
 
-    for (day, part, result) in results {
-        println!("Day {}/{}: {}", day, part, result);
+        assert_eq!(results, vec![
+            (slow, Step::Parse, Err(Failure::Timeout(timeout)), timeout),
+            (other, Step::Parse, Err(Failure::Error("boom".to_string())), Duration::default()),
+        ]);
     }
 }
@@ -1,4 +1,157 @@
-use ::nom::{ alpha, digit };
+use std::str::FromStr;
+use std::{ error, fmt };
+
+use ::nom::{ alpha, digit, Err, IResult };
+
+/// A puzzle input that could not be parsed, and where.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    /// Starting at 1.
+    pub line: usize,
+    /// Starting at 1, in characters.
+    pub column: usize,
+    /// The rest of the offending line, from where parsing failed.
+    pub snippet: String,
+    /// What should have been there (e.g. "an integer").
+    pub expected: String,
+}
+
+impl ParseError {
+    /// An error at byte `offset` of `input`.
+    ///
+    /// ```
+    /// # use advent_solutions::parse::ParseError;
+    /// let error = ParseError::at("1\t2\nthree\t4\n", 4, "an integer");
+    ///
+    /// assert_eq!((error.line, error.column), (2, 1));
+    /// assert_eq!(error.to_string(), "line 2, column 1: expected an integer, found `three\t4`");
+    /// ```
+    pub fn at<S: Into<String>>(input: &str, offset: usize, expected: S) -> ParseError {
+        let mut offset = offset.min(input.len());
+
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line_start = input[..offset].rfind('\n').map_or(0, |x| x + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |x| offset + x);
+
+        ParseError {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[offset..line_end].trim_end_matches('\r').to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// An error at `token`, which must be a slice of `input`.
+    pub fn at_token<S: Into<String>>(input: &str, token: &str, expected: S) -> ParseError {
+        ParseError::at(input, token.as_ptr() as usize - input.as_ptr() as usize, expected)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: expected {}, found ", self.line, self.column, self.expected)?;
+
+        if self.snippet.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "`{}`", self.snippet)
+        }
+    }
+}
+
+impl error::Error for ParseError {}
+
+/// Cleans up a puzzle input however it was saved or pasted: drops a byte
+/// order mark, turns CRLF line endings into LF, and replaces any trailing
+/// whitespace (blank lines included) with a single final newline, adding it
+/// if missing. A blank input becomes empty.
+///
+/// The runner does this before handing inputs to `parse_input`.
+///
+/// ```
+/// # use advent_solutions::parse::normalize;
+/// assert_eq!(normalize("\u{feff}1\r\n2 \r\n\r\n"), "1\n2\n");
+/// assert_eq!(normalize("1122"), "1122\n");
+/// assert_eq!(normalize(" \n"), "");
+/// ```
+pub fn normalize(input: &str) -> String {
+    let input = input.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let input = input.trim_end();
+
+    if input.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", input)
+    }
+}
+
+/// Parses `token` (a slice of `input`), or else fails pointing at it.
+///
+/// ```
+/// # use advent_solutions::parse::token;
+/// let input = "12 x4";
+///
+/// assert_eq!(token::<usize>(input, &input[..2], "an integer"), Ok(12));
+/// assert_eq!(token::<usize>(input, &input[3..], "an integer").unwrap_err().column, 4);
+/// ```
+pub fn token<T: FromStr>(input: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token.parse::<T>()
+        .map_err(|_| ParseError::at_token(input, token, expected))
+}
+
+/// The furthest position into the input that a nom error points at.
+fn error_position<'a>(error: &Err<&'a [u8]>) -> Option<&'a [u8]> {
+    let furthest = |position: Option<&'a [u8]>, errors: &[Err<&'a [u8]>]| {
+        errors.iter()
+            .filter_map(error_position)
+            .chain(position)
+            .min_by_key(|rest| rest.len())
+    };
+
+    match *error {
+        Err::Code(_) => None,
+        Err::Node(_, ref errors) => furthest(None, errors),
+        Err::Position(_, position) => Some(position),
+        Err::NodePosition(_, position, ref errors) => furthest(Some(position), errors),
+    }
+}
+
+/// Turns the result of a nom parser run over all of `input` into a
+/// [`ParseError`](struct.ParseError.html) if it failed or left some input
+/// other than whitespace unparsed, in which case `expected` says what should
+/// have been there.
+///
+/// ```
+/// # #[macro_use] extern crate nom;
+/// # #[macro_use] extern crate advent_solutions;
+/// # use advent_solutions::parse::{ complete, unsigned_number };
+/// # fn main() {
+/// named!(numbers(&[u8]) -> Vec<usize>, lines!(unsigned_number));
+///
+/// let input = "1\n2\nthree\n";
+/// let error = complete(input, numbers(input.as_bytes()), "a number").unwrap_err();
+///
+/// assert_eq!((error.line, error.column), (3, 1));
+/// # }
+/// ```
+pub fn complete<O>(input: &str, result: IResult<&[u8], O>, expected: &str) -> Result<O, ParseError> {
+    let rest = match result {
+        IResult::Done(rest, output) => match rest.iter().position(|x| !x.is_ascii_whitespace()) {
+            // Like the final newline.
+            None => return Ok(output),
+            // List parsers stop before the separator of the item that failed,
+            // so point at the item instead.
+            Some(item) => &rest[item..],
+        },
+        IResult::Error(ref error) => error_position(error).unwrap_or(input.as_bytes()),
+        IResult::Incomplete(_) => &[],
+    };
+
+    Err(ParseError::at(input, input.len() - rest.len(), expected))
+}
 
 #[macro_export]
 macro_rules! to_str(
@@ -0,0 +1,224 @@
+use std::ops;
+
+use ::Direction;
+
+/// A point on a plane, or the vector between two. Like in grids, `y` grows
+/// downwards.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point in space, or the vector between two.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+/// `|a - b|`, without going below zero for unsigned types.
+fn distance<T: PartialOrd + ops::Sub<Output=T>>(a: T, b: T) -> T {
+    if a > b { a - b } else { b - a }
+}
+
+macro_rules! point {
+    ($Point:ident { $first:ident $(, $field:ident)* }) => {
+        impl<T: ops::Add<Output=T>> ops::Add for $Point<T> {
+            type Output = $Point<T>;
+
+            fn add(self, other: $Point<T>) -> $Point<T> {
+                $Point { $first: self.$first + other.$first $(, $field: self.$field + other.$field)* }
+            }
+        }
+
+        impl<T: ops::Sub<Output=T>> ops::Sub for $Point<T> {
+            type Output = $Point<T>;
+
+            fn sub(self, other: $Point<T>) -> $Point<T> {
+                $Point { $first: self.$first - other.$first $(, $field: self.$field - other.$field)* }
+            }
+        }
+
+        impl<T: ops::Neg<Output=T>> ops::Neg for $Point<T> {
+            type Output = $Point<T>;
+
+            fn neg(self) -> $Point<T> {
+                $Point { $first: -self.$first $(, $field: -self.$field)* }
+            }
+        }
+
+        /// Scaling.
+        impl<T: Copy + ops::Mul<Output=T>> ops::Mul<T> for $Point<T> {
+            type Output = $Point<T>;
+
+            fn mul(self, k: T) -> $Point<T> {
+                $Point { $first: self.$first * k $(, $field: self.$field * k)* }
+            }
+        }
+
+        impl<T: ops::AddAssign> ops::AddAssign for $Point<T> {
+            fn add_assign(&mut self, other: $Point<T>) {
+                self.$first += other.$first;
+                $(self.$field += other.$field;)*
+            }
+        }
+
+        impl<T: ops::SubAssign> ops::SubAssign for $Point<T> {
+            fn sub_assign(&mut self, other: $Point<T>) {
+                self.$first -= other.$first;
+                $(self.$field -= other.$field;)*
+            }
+        }
+
+        impl<T: Copy + ops::Add<Output=T> + ops::Mul<Output=T>> $Point<T> {
+            /// The square of the length (i.e. of the distance to the origin).
+            pub fn norm_squared(self) -> T {
+                self.$first * self.$first $(+ self.$field * self.$field)*
+            }
+        }
+
+        impl<T> $Point<T>
+            where T: Copy + PartialOrd + ops::Add<Output=T> + ops::Sub<Output=T>,
+        {
+            /// The sum of the distances along each axis.
+            pub fn manhattan(self, other: $Point<T>) -> T {
+                distance(self.$first, other.$first)
+                    $(+ distance(self.$field, other.$field))*
+            }
+
+            /// The largest of the distances along each axis (i.e. how many
+            /// king moves away `other` is).
+            pub fn chebyshev(self, other: $Point<T>) -> T {
+                let max = distance(self.$first, other.$first);
+
+                $(
+                    let d = distance(self.$field, other.$field);
+                    let max = if d > max { d } else { max };
+                )*
+
+                max
+            }
+        }
+    };
+}
+
+point!(Point2 { x, y });
+point!(Point3 { x, y, z });
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+/// One step towards `direction`.
+impl From<Direction> for Point2<isize> {
+    fn from(direction: Direction) -> Self {
+        Point2::from(Into::<(isize, isize)>::into(direction))
+    }
+}
+
+impl Point2<isize> {
+    /// The point next to this one towards `direction`, unless that overflows.
+    pub fn step(self, direction: Direction) -> Option<Point2<isize>> {
+        let (dx, dy) = direction.into();
+
+        Some(Point2::new(self.x.checked_add(dx)?, self.y.checked_add(dy)?))
+    }
+}
+
+impl Point2<usize> {
+    /// The point next to this one towards `direction`, unless a coordinate
+    /// would go below 0 (or overflow). There is no upper bound: callers must
+    /// check the bottom and right edges of their grid themselves.
+    ///
+    /// ```
+    /// # use advent_solutions::{ Direction, Point2 };
+    /// assert_eq!(Point2::new(0usize, 1).step(Direction::Up), Some(Point2::new(0, 0)));
+    /// assert_eq!(Point2::new(0usize, 1).step(Direction::Left), None);
+    /// ```
+    pub fn step(self, direction: Direction) -> Option<Point2<usize>> {
+        let (dx, dy) = direction.into();
+
+        Some(Point2::new(self.x.checked_add_signed(dx)?, self.y.checked_add_signed(dy)?))
+    }
+}
+
+impl ops::Add<Direction> for Point2<isize> {
+    type Output = Point2<isize>;
+
+    fn add(self, direction: Direction) -> Point2<isize> {
+        self + Point2::from(direction)
+    }
+}
+
+impl ops::AddAssign<Direction> for Point2<isize> {
+    fn add_assign(&mut self, direction: Direction) {
+        *self = *self + direction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::Direction::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point3::new(1, -2, 3);
+
+        assert_eq!(p + Point3::new(1, 1, 1), Point3::new(2, -1, 4));
+        assert_eq!(p - Point3::new(1, 1, 1), Point3::new(0, -3, 2));
+        assert_eq!(-p * 2, Point3::new(-2, 4, -6));
+
+        p += Point3::new(0, 2, 0);
+        p -= Point3::new(1, 0, 0);
+        assert_eq!(p, Point3::new(0, 0, 3));
+
+        let mut q = Point2::new(0isize, 0);
+        q += Up;
+        assert_eq!(q + Right, Point2::new(1, -1));
+        assert_eq!(Point2::new(isize::MAX, 0).step(Right), None);
+    }
+
+    #[test]
+    fn distances() {
+        assert_eq!(Point2::new(1, -2).manhattan(Point2::new(-3, 1)), 7);
+        assert_eq!(Point2::new(1, -2).chebyshev(Point2::new(-3, 1)), 4);
+        assert_eq!(Point3::new(5usize, 0, 2).manhattan(Point3::new(1, 3, 2)), 7);
+        assert_eq!(Point3::new(5usize, 0, 2).chebyshev(Point3::new(1, 3, 2)), 4);
+        assert_eq!(Point3::new(1, -2, 3).norm_squared(), 14);
+    }
+}
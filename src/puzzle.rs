@@ -0,0 +1,647 @@
+//! Puzzle descriptions, converted from the site's HTML to the markdown used
+//! in this crate's docs.
+
+/// Doc lines are wrapped to this width, which leaves room for `/// ` within
+/// 80 columns.
+const WIDTH: usize = 76;
+
+/// A puzzle's description, one markdown string per unlocked part.
+///
+/// ```
+/// # use advent_solutions::Puzzle;
+/// let puzzle = Puzzle::parse(2017, 1, r#"
+/// <article class="day-desc"><h2>--- Day 1: Inverse Captcha ---</h2>
+/// <p>The captcha requires you to review a sequence of <em>digits</em>.</p>
+/// </article>
+/// "#).unwrap();
+///
+/// assert_eq!(puzzle.title, "Day 1: Inverse Captcha");
+/// assert_eq!(puzzle.parts, vec!["The captcha requires you to review a sequence of *digits*.\n"]);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Puzzle {
+    pub year: usize,
+    pub day: usize,
+    pub title: String,
+    pub parts: Vec<String>,
+}
+
+impl Puzzle {
+    /// Parses a puzzle page, or returns `None` if it has no description.
+    pub fn parse(year: usize, day: usize, html: &str) -> Option<Puzzle> {
+        let mut title = None;
+        let mut parts = vec![];
+        let mut rest = html;
+
+        while let Some(start) = rest.find("<article class=\"day-desc\">") {
+            let end = rest[start..].find("</article>")
+                .map_or(rest.len(), |end| start + end);
+
+            let nodes = parse_html(&rest[start..end]);
+
+            if title.is_none() {
+                title = find(&nodes, "h2")
+                    .map(|h2| text(h2).trim_matches(|c| c == '-' || c == ' ').to_string());
+            }
+
+            parts.push(Converter::new(year).markdown(&nodes));
+
+            rest = &rest[end..];
+        }
+
+        Some(Puzzle { year, day, title: title?, parts })
+    }
+
+    /// The title as a markdown heading linking to the puzzle.
+    pub fn heading(&self) -> String {
+        format!("# [{}](http://adventofcode.com/{}/day/{})", self.title, self.year, self.day)
+    }
+}
+
+/// Turns `markdown` into a doc comment, with `prefix` being `//!` or `///`.
+///
+/// ```
+/// # use advent_solutions::doc_comment;
+/// assert_eq!(doc_comment("Some text.\n\nMore.\n", "///"), "/// Some text.\n///\n/// More.\n");
+/// ```
+pub fn doc_comment(markdown: &str, prefix: &str) -> String {
+    markdown.lines()
+        .map(|line| if line.is_empty() {
+            format!("{}\n", prefix)
+        } else {
+            format!("{} {}\n", prefix, line)
+        })
+        .collect()
+}
+
+type Attrs = Vec<(String, String)>;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Node {
+    Element { name: String, attrs: Attrs, children: Vec<Node> },
+    Text(String),
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = s;
+
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let entity = rest.find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| {
+                let c = match &rest[1..end] {
+                    "lt" => Some('<'),
+                    "gt" => Some('>'),
+                    "amp" => Some('&'),
+                    "quot" => Some('"'),
+                    "apos" => Some('\''),
+                    "nbsp" => Some('\u{a0}'),
+                    x if x.starts_with("#x") => u32::from_str_radix(&x[2..], 16).ok()
+                        .and_then(::std::char::from_u32),
+                    x if x.starts_with('#') => x[1..].parse().ok()
+                        .and_then(::std::char::from_u32),
+                    _ => None,
+                };
+
+                c.map(|c| (c, end + 1))
+            });
+
+        match entity {
+            Some((c, len)) => {
+                decoded.push(c);
+                rest = &rest[len..];
+            },
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            },
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn parse_attrs(mut s: &str) -> Attrs {
+    let mut attrs = vec![];
+
+    loop {
+        s = s.trim_start();
+
+        let name_end = s.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(s.len());
+
+        if name_end == 0 {
+            return attrs;
+        }
+
+        let name = s[..name_end].to_lowercase();
+        s = &s[name_end..];
+
+        if !s.starts_with('=') {
+            attrs.push((name, String::new()));
+            continue;
+        }
+
+        s = &s[1..];
+
+        let (value, len) = match s.chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => {
+                let end = s[1..].find(quote).map_or(s.len(), |end| end + 1);
+                (&s[1..end], (end + 1).min(s.len()))
+            },
+            _ => {
+                let end = s.find(char::is_whitespace).unwrap_or(s.len());
+                (&s[..end], end)
+            },
+        };
+
+        attrs.push((name, decode_entities(value)));
+        s = &s[len..];
+    }
+}
+
+/// A forgiving HTML parser, good enough for puzzle descriptions.
+fn parse_html(html: &str) -> Vec<Node> {
+    const VOID: &[&str] = &["br", "hr", "img", "input", "link", "meta"];
+
+    // Each open element, with its children so far.
+    type Open = (String, Attrs, Vec<Node>);
+
+    let mut stack: Vec<Open> = vec![(String::new(), vec![], vec![])];
+
+    fn close(stack: &mut Vec<Open>) {
+        let (name, attrs, children) = stack.pop().unwrap();
+        stack.last_mut().unwrap().2.push(Node::Element { name, attrs, children });
+    }
+
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            rest = &rest[end..];
+        } else if rest.starts_with('<') {
+            let end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let tag = rest[1..end].trim_end_matches('>');
+            rest = &rest[end..];
+
+            if let Some(name) = tag.strip_prefix('/') {
+                let name = name.trim().to_lowercase();
+
+                if stack.iter().skip(1).any(|(open, ..)| *open == name) {
+                    while stack.last().unwrap().0 != name {
+                        close(&mut stack);
+                    }
+
+                    close(&mut stack);
+                }
+            } else if !tag.starts_with('!') {
+                let self_closing = tag.ends_with('/');
+                let tag = tag.trim_end_matches('/');
+
+                let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+                let name = tag[..name_end].to_lowercase();
+                let attrs = parse_attrs(&tag[name_end..]);
+
+                stack.push((name.clone(), attrs, vec![]));
+
+                if self_closing || VOID.contains(&name.as_str()) {
+                    close(&mut stack);
+                }
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            stack.last_mut().unwrap().2.push(Node::Text(decode_entities(&rest[..end])));
+            rest = &rest[end..];
+        }
+    }
+
+    while stack.len() > 1 {
+        close(&mut stack);
+    }
+
+    stack.pop().unwrap().2
+}
+
+fn find<'a>(nodes: &'a [Node], tag: &str) -> Option<&'a Node> {
+    nodes.iter()
+        .filter_map(|node| match *node {
+            Node::Element { ref name, .. } if name == tag => Some(node),
+            Node::Element { ref children, .. } => find(children, tag),
+            Node::Text(_) => None,
+        })
+        .next()
+}
+
+fn text(node: &Node) -> String {
+    match *node {
+        Node::Element { ref children, .. } => children.iter().map(text).collect(),
+        Node::Text(ref text) => text.clone(),
+    }
+}
+
+fn attr<'a>(attrs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attrs.iter()
+        .find(|(attr, _)| attr == name)
+        .map(|(_, value)| value.as_str())
+}
+
+/// Stands for a space that must not be wrapped at (e.g. inside code).
+const NO_BREAK: char = '\u{0}';
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+enum Block {
+    Paragraph(String),
+    Code(String),
+    List(Vec<Vec<Block>>),
+}
+
+struct Converter {
+    year: usize,
+    /// Reference-style links, as `(label, url)`.
+    links: Vec<(String, String)>,
+}
+
+impl Converter {
+    fn new(year: usize) -> Converter {
+        Converter { year, links: vec![] }
+    }
+
+    fn markdown(&mut self, nodes: &[Node]) -> String {
+        let article = match find(nodes, "article") {
+            Some(Node::Element { children, .. }) => &children[..],
+            _ => nodes,
+        };
+
+        let blocks = self.blocks(article);
+        let mut lines = render(&blocks, WIDTH);
+
+        if !self.links.is_empty() {
+            lines.push(String::new());
+
+            for (label, url) in &self.links {
+                lines.push(format!("  [{}]: {}", label, url));
+            }
+        }
+
+        lines.into_iter()
+            .map(|line| line + "\n")
+            .collect()
+    }
+
+    fn blocks(&mut self, nodes: &[Node]) -> Vec<Block> {
+        let mut blocks = vec![];
+        let mut inline = String::new();
+
+        for node in nodes {
+            let block = match *node {
+                Node::Element { ref name, ref children, .. } => match name.as_str() {
+                    "p" | "div" => Some(Block::Paragraph(self.inlines(children))),
+                    "pre" => Some(Block::Code(text(node).trim_end_matches('\n').to_string())),
+                    "ul" | "ol" => Some(Block::List(
+                        children.iter()
+                            .filter_map(|child| match *child {
+                                Node::Element { ref name, ref children, .. } if name == "li" => {
+                                    Some(self.blocks(children))
+                                },
+                                _ => None,
+                            })
+                            .collect()
+                    )),
+                    "h2" | "script" | "style" => Some(Block::Paragraph(String::new())),
+                    _ => None,
+                },
+                Node::Text(_) => None,
+            };
+
+            match block {
+                Some(block) => {
+                    push_paragraph(&mut blocks, &inline);
+                    inline.clear();
+
+                    match block {
+                        Block::Paragraph(ref text) if text.trim().is_empty() => {},
+                        block => blocks.push(block),
+                    }
+                },
+                None => inline.push_str(&self.inline(node)),
+            }
+        }
+
+        push_paragraph(&mut blocks, &inline);
+
+        blocks
+    }
+
+    fn inlines(&mut self, nodes: &[Node]) -> String {
+        nodes.iter()
+            .map(|node| self.inline(node))
+            .collect()
+    }
+
+    fn inline(&mut self, node: &Node) -> String {
+        let (name, attrs, children) = match *node {
+            Node::Element { ref name, ref attrs, ref children } => (name, attrs, children),
+            Node::Text(ref text) => return escape(&text.replace('\n', " ")),
+        };
+
+        match name.as_str() {
+            "em" | "i" | "b" | "strong" => {
+                let content = self.inlines(children);
+
+                let marker = if attr(attrs, "class") == Some("star") || name == "b" || name == "strong" {
+                    "**"
+                } else {
+                    "*"
+                };
+
+                wrap_trimmed(&content, marker, marker)
+            },
+            "code" => {
+                let code = text(node).split_whitespace().collect::<Vec<_>>().join(" ");
+                let ticks = if code.contains('`') { "``" } else { "`" };
+
+                format!("{}{}{}", ticks, code, ticks).replace(' ', &NO_BREAK.to_string())
+            },
+            "a" => {
+                let content = self.inlines(children);
+
+                match attr(attrs, "href") {
+                    Some(href) => {
+                        let label = self.link(content.trim(), href);
+
+                        if label == content.trim() {
+                            wrap_trimmed(&content, "[", "]")
+                        } else {
+                            wrap_trimmed(&content, "[", &format!("][{}]", label))
+                        }
+                    },
+                    None => content,
+                }
+            },
+            "span" => match attr(attrs, "title") {
+                Some(title) => format!(
+                    "<span title=\"{}\">{}</span>",
+                    title.replace('"', "&quot;"),
+                    self.inlines(children)
+                ),
+                None => self.inlines(children),
+            },
+            "s" | "sup" | "sub" => format!("<{0}>{1}</{0}>", name, self.inlines(children)),
+            "br" => "\n".to_string(),
+            _ => self.inlines(children),
+        }
+    }
+
+    /// Registers a link, returning its label.
+    fn link(&mut self, text: &str, href: &str) -> String {
+        let url = self.url(href);
+        let mut label = text.to_string();
+        let mut n = 1;
+
+        loop {
+            let existing = self.links.iter()
+                .find(|(x, _)| x.to_lowercase() == label.to_lowercase());
+
+            match existing {
+                Some((_, existing)) if *existing == url => return label,
+                Some(_) => {
+                    n += 1;
+                    label = format!("{} {}", text, n);
+                },
+                None => {
+                    self.links.push((label.clone(), url));
+                    return label;
+                },
+            }
+        }
+    }
+
+    /// Links to other days of the same year point to their docs.
+    fn url(&self, href: &str) -> String {
+        let same_year = format!("/{}/day/", self.year);
+
+        if href.starts_with(&same_year) {
+            if let Ok(day) = href[same_year.len()..].parse::<usize>() {
+                return format!("../day{:02}/index.html", day);
+            }
+        }
+
+        if href.starts_with('/') {
+            format!("http://adventofcode.com{}", href)
+        } else {
+            href.to_string()
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('*', "\\*")
+        .replace('`', "\\`")
+}
+
+/// Wraps `content` in `open` and `close`, leaving any surrounding whitespace
+/// outside.
+fn wrap_trimmed(content: &str, open: &str, close: &str) -> String {
+    let trimmed = content.trim();
+
+    if trimmed.is_empty() {
+        return content.to_string();
+    }
+
+    let start = content.find(trimmed).unwrap();
+
+    format!(
+        "{}{}{}{}{}",
+        &content[..start],
+        open,
+        trimmed,
+        close,
+        &content[start + trimmed.len()..]
+    )
+}
+
+fn push_paragraph(blocks: &mut Vec<Block>, inline: &str) {
+    if !inline.trim().is_empty() {
+        blocks.push(Block::Paragraph(inline.to_string()));
+    }
+}
+
+/// Word-wraps `text` to `width` columns.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > width {
+                lines.push(line);
+                line = String::new();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+
+            line.push_str(word);
+        }
+
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+
+    lines.into_iter()
+        .map(|line| line.replace(NO_BREAK, " "))
+        .collect()
+}
+
+fn render(blocks: &[Block], width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut previous = None;
+
+    for block in blocks {
+        if let Some(previous) = previous {
+            lines.push(String::new());
+
+            // Keep consecutive lists apart.
+            if let (&Block::List(_), &Block::List(_)) = (previous, block) {
+                lines.push("<!-- -->".to_string());
+                lines.push(String::new());
+            }
+        }
+
+        match *block {
+            Block::Paragraph(ref text) => lines.extend(wrap(text, width)),
+            Block::Code(ref code) => {
+                lines.push("```text".to_string());
+                lines.extend(code.lines().map(|line| line.to_string()));
+                lines.push("```".to_string());
+            },
+            Block::List(ref items) => {
+                let loose = items.iter().any(|item| item.len() > 1);
+
+                for (i, item) in items.iter().enumerate() {
+                    if loose && i > 0 {
+                        lines.push(String::new());
+                    }
+
+                    for (j, line) in render(item, width - 4).into_iter().enumerate() {
+                        lines.push(match (j, line.is_empty()) {
+                            (0, _) => format!("-   {}", line),
+                            (_, true) => line,
+                            (_, false) => format!("    {}", line),
+                        });
+                    }
+                }
+            },
+        }
+
+        previous = Some(block);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html><body><main>
+<article class="day-desc"><h2>--- Day 10: Knot Hash ---</h2><p>You come across some programs that are trying to implement a software emulation of a hash based on knot-tying. The hash these programs are implementing isn't very strong, but you decide to help them anyway. You make a mental note to remind the Elves later not to <span title="Or in hash functions.">invent their own cryptographic functions</span>.</p>
+<p>To achieve this, begin with a <em>list</em> of numbers from <code>0</code> to <code>255</code>, a <em>current position</em> which begins at <code>0</code>:</p>
+<ul>
+<li><em>Reverse</em> the order of that <em>length</em> of elements in the <em>list</em>.</li>
+<li><em>Increase</em> the <em>skip size</em> by one.</li>
+</ul>
+<ul>
+<li>The list begins as <code>[0] 1 2 3 4</code>.</li>
+</ul>
+<pre><code>5 1 9 5
+7 5 3
+</code></pre>
+<p>As in the <a href="/2017/day/5">jump instructions</a>, see <a href="https://en.wikipedia.org/wiki/Hexadecimal" target="_blank">hexadecimal</a> &amp; <em class="star">stars</em>.</p>
+</article>
+<p>Your puzzle answer was <code>4480</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>The logic you've constructed forms a single <em>round</em> of the <em>Knot Hash</em> algorithm.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn parse() {
+        let puzzle = Puzzle::parse(2017, 10, PAGE).unwrap();
+
+        assert_eq!(puzzle.title, "Day 10: Knot Hash");
+        assert_eq!(puzzle.heading(), "# [Day 10: Knot Hash](http://adventofcode.com/2017/day/10)");
+        assert_eq!(puzzle.parts.len(), 2);
+
+        assert_eq!(puzzle.parts[0], "\
+You come across some programs that are trying to implement a software
+emulation of a hash based on knot-tying. The hash these programs are
+implementing isn't very strong, but you decide to help them anyway. You make
+a mental note to remind the Elves later not to <span title=\"Or in hash
+functions.\">invent their own cryptographic functions</span>.
+
+To achieve this, begin with a *list* of numbers from `0` to `255`, a
+*current position* which begins at `0`:
+
+-   *Reverse* the order of that *length* of elements in the *list*.
+-   *Increase* the *skip size* by one.
+
+<!-- -->
+
+-   The list begins as `[0] 1 2 3 4`.
+
+```text
+5 1 9 5
+7 5 3
+```
+
+As in the [jump instructions], see [hexadecimal] & **stars**.
+
+  [jump instructions]: ../day05/index.html
+  [hexadecimal]: https://en.wikipedia.org/wiki/Hexadecimal
+");
+
+        assert_eq!(
+            puzzle.parts[1],
+            "The logic you've constructed forms a single *round* of the *Knot Hash*\nalgorithm.\n"
+        );
+    }
+
+    #[test]
+    fn no_description() {
+        assert_eq!(Puzzle::parse(2017, 1, "<p>Not found</p>"), None);
+    }
+
+    #[test]
+    fn entities() {
+        assert_eq!(decode_entities("&lt;a&gt; &amp;&#39;&#x41; & &bogus;"), "<a> &'A & &bogus;");
+    }
+
+    #[test]
+    fn loose_lists() {
+        let nodes = parse_html("<ul><li>One.<pre><code>1\n</code></pre></li><li>Two.</li></ul>");
+
+        assert_eq!(
+            Converter::new(2017).markdown(&nodes),
+            "-   One.\n\n    ```text\n    1\n    ```\n\n-   Two.\n"
+        );
+    }
+
+    #[test]
+    fn link_labels() {
+        let mut converter = Converter::new(2017);
+
+        assert_eq!(converter.inline(&parse_html("<a href=\"/a\">x</a>")[0]), "[x]");
+        assert_eq!(converter.inline(&parse_html("<a href=\"/a\">x</a>")[0]), "[x]");
+        assert_eq!(converter.inline(&parse_html("<a href=\"/b\">x</a>")[0]), "[x][x 2]");
+        assert_eq!(converter.links[0], ("x".to_string(), "http://adventofcode.com/a".to_string()));
+    }
+}
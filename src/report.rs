@@ -0,0 +1,382 @@
+//! Runner output, as text, JSON or CSV.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::time::Duration;
+
+use itertools::Itertools;
+
+use advent_solutions::{ Answer, Answers, Verdict };
+use Step;
+
+/// Why a step has no result.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Failure {
+    Error(String),
+    /// It was given up on after this long.
+    Timeout(Duration),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Failure::Error(ref e) => write!(f, "{}", e),
+            Failure::Timeout(timeout) => write!(f, "timed out after {:?}", timeout),
+        }
+    }
+}
+
+/// The outcome of one step of a day, as sent back by the jobs.
+pub type StepResult = ((usize, usize), Step, Result<Option<Answer>, Failure>, Duration);
+
+/// Everything known about one part of a day.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Record {
+    pub year: usize,
+    pub day: usize,
+    pub part: usize,
+    pub answer: Option<Answer>,
+    pub parse_time: Option<Duration>,
+    pub time: Option<Duration>,
+    pub verdict: Option<Verdict>,
+    /// Why there is no answer (input, parsing or the part itself failed).
+    pub error: Option<Failure>,
+}
+
+impl Record {
+    pub fn status(&self) -> &'static str {
+        match self.verdict {
+            _ if matches!(self.error, Some(Failure::Timeout(_))) => "TIMEOUT",
+            _ if self.error.is_some() => "FAILED",
+            Some(Verdict::Ok) => "OK",
+            Some(Verdict::Wrong(_)) => "WRONG",
+            _ => "UNKNOWN",
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        self.error.is_some() || matches!(self.verdict, Some(Verdict::Wrong(_)))
+    }
+
+    fn expected(&self) -> Option<&str> {
+        match self.verdict {
+            Some(Verdict::Wrong(ref expected)) => Some(expected),
+            _ => None,
+        }
+    }
+}
+
+/// Builds one record for each selected part of each day in `results`,
+/// checking answers against the known ones for each year.
+pub fn records(
+    mut results: Vec<StepResult>,
+    parts: &[usize],
+    answers: &HashMap<usize, Answers>,
+) -> Vec<Record> {
+    results.sort_by_key(|&(key, step, ..)| (key, step));
+
+    let mut records = vec![];
+
+    for ((year, day), steps) in &results.into_iter().group_by(|&(key, ..)| key) {
+        let steps = steps.collect::<Vec<_>>();
+
+        let failed_step = steps.iter()
+            .find(|&&(_, step, ref result, _)| step < Step::Part1 && result.is_err());
+
+        let parse_time = steps.iter()
+            .find(|&&(_, step, ..)| step == Step::Parse)
+            .map(|&(.., elapsed)| elapsed);
+
+        for &part in parts {
+            let mut record = Record {
+                year,
+                day,
+                part,
+                answer: None,
+                parse_time,
+                time: None,
+                verdict: None,
+                error: None,
+            };
+
+            match failed_step {
+                Some(&(_, step, Err(Failure::Error(ref e)), _)) => {
+                    record.error = Some(Failure::Error(format!("{}: {}", step, e)));
+                },
+                Some(&(_, _, Err(ref timeout), _)) => record.error = Some(timeout.clone()),
+                _ => {},
+            }
+
+            let result = steps.iter()
+                .find(|&&(_, step, ..)| step as usize - 1 == part);
+
+            if let Some(&(_, _, ref result, elapsed)) = result {
+                record.time = Some(elapsed);
+
+                match *result {
+                    Ok(Some(ref answer)) => {
+                        record.verdict = answers.get(&year)
+                            .map(|answers| answers.verify(day, part, answer));
+                        record.answer = Some(answer.clone());
+                    },
+                    Ok(None) => {},
+                    Err(ref e) => record.error = Some(e.clone()),
+                }
+            }
+
+            records.push(record);
+        }
+    }
+
+    records
+}
+
+/// A `Day NN/P: ...` line for each part, which `Answers::parse` can read
+/// back.
+/// Whether `records` span several years, so that each day needs its year.
+fn several_years(records: &[Record]) -> bool {
+    records.iter().map(|record| record.year).unique().count() > 1
+}
+
+fn answer_lines(records: &[Record]) -> Vec<String> {
+    let years = several_years(records);
+
+    records.iter()
+        .filter_map(|record| {
+            let label = if years {
+                format!("{} Day {:02}/{}", record.year, record.day, record.part)
+            } else {
+                format!("Day {:02}/{}", record.day, record.part)
+            };
+
+            match (&record.answer, &record.error) {
+                (_, Some(Failure::Timeout(timeout))) => Some(format!("{}: TIMEOUT after {:?}", label, timeout)),
+                (_, Some(e)) => Some(format!("{}: FAILED: {}", label, e)),
+                (Some(answer), _) => Some(format!(
+                    "{}: {} {}",
+                    label,
+                    answer,
+                    record.verdict.as_ref().unwrap_or(&Verdict::Unknown)
+                )),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+pub fn print_text(records: &[Record]) {
+    for line in answer_lines(records) {
+        println!("{}", line);
+    }
+
+    let years = several_years(records);
+    let width = if years { 10 } else { 5 };
+
+    println!();
+    println!(
+        "{:<width$}{:>12} {:>12} {:>12}",
+        if years { "Year Day" } else { "Day" }, "Parse", "Part 1", "Part 2",
+        width = width
+    );
+
+    let timed = records.iter()
+        .filter(|record| record.parse_time.is_some());
+
+    for ((year, day), records) in &timed.group_by(|record| (record.year, record.day)) {
+        let mut timings = [String::new(), String::new(), String::new()];
+
+        for record in records {
+            timings[0] = record.parse_time.map(|x| format!("{:?}", x)).unwrap_or_default();
+            timings[record.part] = record.time.map(|x| format!("{:?}", x)).unwrap_or_default();
+        }
+
+        let label = if years { format!("{} {:02}", year, day) } else { format!("{:02}", day) };
+
+        println!("{:<width$}{:>12} {:>12} {:>12}", label, timings[0], timings[1], timings[2], width = width);
+    }
+
+    let failed = records.iter().filter(|record| record.failed()).count();
+
+    if failed > 0 {
+        println!();
+        println!("{} part(s) FAILED, TIMEOUT or WRONG", failed);
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from("\"");
+
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+
+    json.push('"');
+    json
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match *answer {
+        Answer::None => "null".to_string(),
+        Answer::Signed(x) => x.to_string(),
+        Answer::Unsigned(x) => x.to_string(),
+        Answer::String(ref x) => json_string(x),
+    }
+}
+
+fn json_or_null<T, F: Fn(T) -> String>(value: Option<T>, f: F) -> String {
+    value.map(f).unwrap_or_else(|| "null".to_string())
+}
+
+pub fn print_json(records: &[Record]) {
+    println!("[");
+
+    for (i, record) in records.iter().enumerate() {
+        println!(
+            "  {{\"year\": {}, \"day\": {}, \"part\": {}, \"answer\": {}, \"type\": {}, \
+             \"parse_ns\": {}, \"time_ns\": {}, \"status\": {}, \"expected\": {}, \"error\": {}}}{}",
+            record.year,
+            record.day,
+            record.part,
+            json_or_null(record.answer.as_ref(), json_answer),
+            json_or_null(record.answer.as_ref(), |x| json_string(x.kind())),
+            json_or_null(record.parse_time, |x| x.as_nanos().to_string()),
+            json_or_null(record.time, |x| x.as_nanos().to_string()),
+            json_string(record.status()),
+            json_or_null(record.expected(), json_string),
+            json_or_null(record.error.as_ref(), |x| json_string(&x.to_string())),
+            if i + 1 < records.len() { "," } else { "" },
+        );
+    }
+
+    println!("]");
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn print_csv(records: &[Record]) {
+    println!("year,day,part,answer,type,parse_ns,time_ns,status,expected,error");
+
+    for record in records {
+        println!(
+            "{},{},{},{},{},{},{},{},{},{}",
+            record.year,
+            record.day,
+            record.part,
+            record.answer.as_ref().map(|x| csv_field(&x.to_string())).unwrap_or_default(),
+            record.answer.as_ref().map(|x| x.kind()).unwrap_or_default(),
+            record.parse_time.map(|x| x.as_nanos().to_string()).unwrap_or_default(),
+            record.time.map(|x| x.as_nanos().to_string()).unwrap_or_default(),
+            record.status(),
+            record.expected().map(csv_field).unwrap_or_default(),
+            record.error.as_ref().map(|x| csv_field(&x.to_string())).unwrap_or_default(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escaping() {
+        assert_eq!(json_string("a\"b\\c\nd\u{1}"), "\"a\\\"b\\\\c\\nd\\u0001\"");
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+
+    #[test]
+    fn records_per_part() {
+        let mut answers = HashMap::new();
+        answers.insert(2017, Answers::parse("Day 01/1: 3\nDay 01/2: 5"));
+
+        let ms = Duration::from_millis(1);
+
+        let results = vec![
+            ((2017, 1), Step::Part2, Ok(Some(Answer::from(4usize))), ms),
+            ((2017, 1), Step::Parse, Ok(None), ms),
+            ((2017, 1), Step::Part1, Ok(Some(Answer::from(3usize))), ms),
+            ((2017, 2), Step::Parse, Err(Failure::Error("boom".to_string())), ms),
+            ((2017, 3), Step::Parse, Ok(None), ms),
+            ((2017, 3), Step::Part1, Ok(Some(Answer::from(1usize))), ms),
+            ((2017, 3), Step::Part2, Err(Failure::Timeout(ms)), ms),
+        ];
+
+        let records = records(results, &[1, 2], &answers);
+
+        assert_eq!(records.len(), 6);
+        assert_eq!(records.iter().map(|x| x.status()).collect::<Vec<_>>(),
+                   vec!["OK", "WRONG", "FAILED", "FAILED", "UNKNOWN", "TIMEOUT"]);
+        assert_eq!(records[1].expected(), Some("5"));
+        assert_eq!(records[2].error, Some(Failure::Error("parse: boom".to_string())));
+        assert_eq!(records[2].answer, None);
+        assert!(records[5].failed());
+    }
+
+    #[test]
+    fn pasted_answers() {
+        let mut answers = HashMap::new();
+        answers.insert(2017, Answers::parse("Day 01/1: 3\nDay 01/2: 5"));
+
+        let ms = Duration::from_millis(1);
+
+        let results = vec![
+            ((2017, 1), Step::Parse, Ok(None), ms),
+            ((2017, 1), Step::Part1, Ok(Some(Answer::from(3usize))), ms),
+            ((2017, 1), Step::Part2, Ok(Some(Answer::from(4usize))), ms),
+            ((2017, 2), Step::Parse, Ok(None), ms),
+            ((2017, 2), Step::Part1, Ok(Some(Answer::from("abc"))), ms),
+            ((2017, 2), Step::Part2, Ok(Some(Answer::None)), ms),
+            ((2017, 3), Step::Parse, Ok(None), ms),
+            ((2017, 3), Step::Part1, Err(Failure::Error("boom".to_string())), ms),
+            ((2017, 3), Step::Part2, Err(Failure::Timeout(ms)), ms),
+        ];
+
+        let records = records(results, &[1, 2], &answers);
+        let pasted = Answers::parse(&answer_lines(&records).join("\n"));
+
+        for record in &records {
+            let verdict = match record.answer {
+                Some(ref answer) => pasted.verify(record.day, record.part, answer),
+                None => pasted.verify(record.day, record.part, &Answer::from("boom")),
+            };
+
+            let expected = if record.answer.is_some() { Verdict::Ok } else { Verdict::Unknown };
+
+            assert_eq!(verdict, expected, "{:?}", record);
+        }
+    }
+    #[test]
+    fn several_years() {
+        let ms = Duration::from_millis(1);
+
+        let results = vec![
+            ((2016, 1), Step::Parse, Ok(None), ms),
+            ((2016, 1), Step::Part1, Ok(Some(Answer::from(3usize))), ms),
+            ((2017, 1), Step::Parse, Ok(None), ms),
+            ((2017, 1), Step::Part1, Err(Failure::Error("boom".to_string())), ms),
+        ];
+
+        assert_eq!(answer_lines(&records(results.clone(), &[1], &HashMap::new())), vec![
+            "2016 Day 01/1: 3 UNKNOWN",
+            "2017 Day 01/1: FAILED: boom",
+        ]);
+
+        assert_eq!(answer_lines(&records(results[2..].to_vec(), &[1], &HashMap::new())), vec![
+            "Day 01/1: FAILED: boom",
+        ]);
+    }
+}
@@ -0,0 +1,384 @@
+//! The `new` command, which creates the module for a new day, and the
+//! `puzzle` command, which prints its docs.
+
+use std::fs::{ self, OpenOptions };
+use std::io::Write;
+use std::path::{ Path, PathBuf };
+
+use advent_solutions::{ doc_comment, Downloader, Puzzle };
+use cli::{ NewOptions, PuzzleOptions };
+
+fn title(name: &str) -> String {
+    name.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+
+            chars.next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The module docs (the title and part 1) and part 2's docs, if unlocked.
+fn docs(puzzle: &Puzzle) -> (String, Option<String>) {
+    let mut module = puzzle.heading() + "\n";
+
+    if let Some(part1) = puzzle.parts.first() {
+        module = module + "\n" + part1;
+    }
+
+    (
+        doc_comment(&module, "//!"),
+        puzzle.parts.get(1).map(|part2| doc_comment(part2, "///")),
+    )
+}
+
+fn day_template(options: &NewOptions, puzzle: Option<&Puzzle>) -> String {
+    let (module_docs, part2_docs) = match puzzle {
+        Some(puzzle) => docs(puzzle),
+        None => (
+            format!(
+                "//! # [Day {day}: {title}](http://adventofcode.com/{year}/day/{day})\n",
+                year = options.year,
+                day = options.day,
+                title = title(&options.name),
+            ),
+            None,
+        ),
+    };
+
+    format!(
+        "{module_docs}
+use ::ParseError;
+
+pub fn parse_input(input: &str) -> Result<&str, ParseError> {{
+    Ok(input)
+}}
+
+pub fn part1(_input: &str) -> usize {{
+    unimplemented!()
+}}
+
+{part2_docs}pub fn part2(_input: &str) -> usize {{
+    unimplemented!()
+}}
+
+solution!(&'a str);
+
+test_day!({year}, \"{day:02}\", 0, 0);
+",
+        module_docs = module_docs,
+        part2_docs = part2_docs.unwrap_or_default(),
+        year = options.year,
+        day = options.day,
+    )
+}
+
+fn mod_line(options: &NewOptions) -> String {
+    format!("#[path=\"{:02}-{}.rs\"] pub mod day{:02};", options.day, options.name, options.day)
+}
+
+fn registry_line(options: &NewOptions) -> String {
+    format!("        Day::new::<day{:02}::Solver>(YEAR, {}),", options.day, options.day)
+}
+
+fn year_template(options: &NewOptions) -> String {
+    format!(
+        "//! Solutions for [Advent of Code {year}].
+//!
+//!  [Advent of Code {year}]: http://adventofcode.com/{year}
+
+{mod_line}
+
+use ::Day;
+
+pub const YEAR: usize = {year};
+
+/// Every solved day, to be included in the [`registry`](../fn.registry.html).
+pub fn days() -> Vec<Day> {{
+    vec![
+{registry_line}
+    ]
+}}
+",
+        year = options.year,
+        mod_line = mod_line(options),
+        registry_line = registry_line(options),
+    )
+}
+
+/// Inserts `line` among the lines starting with `prefix`, keeping them sorted.
+fn insert_sorted(source: &str, prefix: &str, line: &str) -> Result<String, String> {
+    let mut lines = source.lines().collect::<Vec<_>>();
+
+    let matching = lines.iter()
+        .enumerate()
+        .filter(|&(_, x)| x.trim_start().starts_with(prefix))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+
+    let last = *matching.last()
+        .ok_or_else(|| format!("Could not find `{}`", prefix))?;
+
+    let index = matching.into_iter()
+        .find(|&i| lines[i].trim() > line.trim())
+        .unwrap_or(last + 1);
+
+    lines.insert(index, line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Adds `advent<year>` to the `years!` list in `lib.rs`.
+fn add_year(lib: &str, year: usize) -> Result<String, String> {
+    let start = lib.find("\nyears!(")
+        .ok_or("Could not find `years!` in lib.rs")? + "\nyears!(".len();
+    let end = start + lib[start..].find(')')
+        .ok_or("Could not find the end of `years!` in lib.rs")?;
+
+    let mut years = lib[start..end].split(',')
+        .map(|x| x.trim().to_string())
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>();
+
+    years.push(format!("advent{}", year));
+    years.sort();
+
+    Ok(format!("{}{}{}", &lib[..start], years.join(", "), &lib[end..]))
+}
+
+/// Creates the files for a new day under `root` (the repository), returning
+/// the paths of the files created or changed.
+pub fn scaffold(
+    root: &Path,
+    options: &NewOptions,
+    puzzle: Option<&Puzzle>,
+) -> Result<Vec<PathBuf>, String> {
+    let io_error = |path: &Path| {
+        let path = path.display().to_string();
+        move |e| format!("{}: {}", path, e)
+    };
+
+    let lib_path = root.join("src").join("lib.rs");
+    let year_dir = root.join("src").join(format!("advent{}", options.year));
+    let mod_path = year_dir.join("mod.rs");
+    let day_path = year_dir.join(format!("{:02}-{}.rs", options.day, options.name));
+
+    if !lib_path.is_file() {
+        return Err(format!("{} not found (run from the repository root)", lib_path.display()));
+    }
+
+    let mut changed = vec![];
+
+    if mod_path.is_file() {
+        let source = fs::read_to_string(&mod_path).map_err(io_error(&mod_path))?;
+
+        if source.contains(&format!("pub mod day{:02};", options.day)) {
+            return Err(format!(
+                "Day {} of {} already exists in {}", options.day, options.year, mod_path.display()
+            ));
+        }
+
+        let source = insert_sorted(&source, "#[path=", &mod_line(options))?;
+        let source = insert_sorted(&source, "Day::new::<", &registry_line(options))?;
+
+        write_new(&day_path, &day_template(options, puzzle))?;
+        fs::write(&mod_path, source).map_err(io_error(&mod_path))?;
+    } else {
+        let lib = fs::read_to_string(&lib_path).map_err(io_error(&lib_path))?;
+        let lib = add_year(&lib, options.year)?;
+
+        fs::create_dir_all(&year_dir).map_err(io_error(&year_dir))?;
+        write_new(&day_path, &day_template(options, puzzle))?;
+        write_new(&mod_path, &year_template(options))?;
+        fs::write(&lib_path, lib).map_err(io_error(&lib_path))?;
+
+        changed.push(lib_path);
+    }
+
+    changed.insert(0, mod_path);
+    changed.insert(0, day_path);
+
+    Ok(changed)
+}
+
+/// Writes `contents` to `path`, which must not exist yet.
+fn write_new(path: &Path, contents: &str) -> Result<(), String> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+pub fn run(options: NewOptions) -> Result<bool, String> {
+    let valid_name = !options.name.is_empty()
+        && options.name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+    if !valid_name {
+        return Err(format!(
+            "Invalid --name `{}` (use lowercase letters, digits and `_`)", options.name
+        ));
+    }
+
+    if options.day < 1 || options.day > 25 {
+        return Err(format!("Invalid --day `{}` (must be 1 to 25)", options.day));
+    }
+
+    let downloader = Downloader::new();
+
+    let puzzle = downloader.as_ref()
+        .unwrap_or(&Downloader::cache_only())
+        .puzzle(options.year, options.day)
+        .map_err(|e| eprintln!("Could not download the puzzle: {}", e))
+        .ok();
+
+    for path in scaffold(Path::new(""), &options, puzzle.as_ref())? {
+        println!("Wrote {}", path.display());
+    }
+
+    let input_path = Path::new("test_inputs")
+        .join(options.year.to_string())
+        .join(format!("{:02}", options.day));
+
+    if input_path.is_file() {
+        println!("Kept {}", input_path.display());
+        return Ok(true);
+    }
+
+    // The downloader caches inputs as `<year>/<day>`, just like test_inputs.
+    let input = downloader
+        .and_then(|downloader| downloader.cache_dir("test_inputs").input(options.year, options.day));
+
+    match input {
+        Ok(_) => println!("Wrote {}", input_path.display()),
+        Err(e) => {
+            eprintln!("Could not download the input: {}", e);
+
+            // `test_day!` needs the file to exist.
+            fs::create_dir_all(input_path.parent().unwrap())
+                .and_then(|_| fs::write(&input_path, ""))
+                .map_err(|e| format!("{}: {}", input_path.display(), e))?;
+
+            println!("Wrote {} (empty, paste your input there)", input_path.display());
+        },
+    }
+
+    Ok(true)
+}
+
+/// Prints a day's docs, e.g. to update them once part 2 is unlocked.
+pub fn print_docs(options: PuzzleOptions) -> Result<bool, String> {
+    let puzzle = Downloader::new()
+        .unwrap_or_else(|e| {
+            eprintln!("{} (only part 1 is available)", e);
+            Downloader::cache_only()
+        })
+        .puzzle(options.year, options.day)
+        .map_err(|e| e.to_string())?;
+
+    let (module_docs, part2_docs) = docs(&puzzle);
+
+    print!("{}", module_docs);
+
+    if let Some(part2_docs) = part2_docs {
+        println!();
+        print!("{}", part2_docs);
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn options(year: usize, day: usize, name: &str) -> NewOptions {
+        NewOptions { year, day, name: name.to_string() }
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(title("inverse_captcha"), "Inverse Captcha");
+        assert_eq!(title("duet"), "Duet");
+    }
+
+    #[test]
+    fn puzzle_docs() {
+        let puzzle = Puzzle {
+            year: 2017,
+            day: 1,
+            title: "Day 1: Inverse Captcha".to_string(),
+            parts: vec!["Part *one*.\n".to_string(), "Part two.\n".to_string()],
+        };
+
+        let template = day_template(&options(2017, 1, "inverse_captcha"), Some(&puzzle));
+
+        assert!(template.starts_with("\
+//! # [Day 1: Inverse Captcha](http://adventofcode.com/2017/day/1)
+//!
+//! Part *one*.
+
+use ::ParseError;
+
+pub fn parse_input"));
+        assert!(template.contains("}\n\n/// Part two.\npub fn part2"));
+
+        assert!(day_template(&options(2017, 1, "inverse_captcha"), None)
+            .starts_with("//! # [Day 1: Inverse Captcha](http://adventofcode.com/2017/day/1)\n\nuse"));
+    }
+
+    #[test]
+    fn sorted_lines() {
+        let source = "a\n#[path=\"01-a.rs\"] pub mod day01;\n#[path=\"05-b.rs\"] pub mod day05;\nb\n";
+
+        assert_eq!(
+            insert_sorted(source, "#[path=", "#[path=\"03-c.rs\"] pub mod day03;"),
+            Ok("a\n#[path=\"01-a.rs\"] pub mod day01;\n#[path=\"03-c.rs\"] pub mod day03;\n\
+                #[path=\"05-b.rs\"] pub mod day05;\nb\n".to_string())
+        );
+
+        assert_eq!(
+            add_year("x\nyears!(advent2017);\n", 2015),
+            Ok("x\nyears!(advent2015, advent2017);\n".to_string())
+        );
+    }
+
+    #[test]
+    fn new_day() {
+        let root = env::temp_dir().join(format!("advent_scaffold_{}", process::id()));
+        let src = root.join("src");
+
+        fs::create_dir_all(src.join("advent2017")).unwrap();
+        fs::write(src.join("lib.rs"), "mod x;\nyears!(advent2017);\n").unwrap();
+        fs::write(
+            src.join("advent2017").join("mod.rs"),
+            "#[path=\"01-a.rs\"] pub mod day01;\n\nfn days() {\n    vec![\n        \
+             Day::new::<day01::Solver>(YEAR, 1),\n    ]\n}\n",
+        ).unwrap();
+
+        scaffold(&root, &options(2017, 2, "b"), None).unwrap();
+
+        let source = fs::read_to_string(src.join("advent2017").join("mod.rs")).unwrap();
+        assert!(source.contains("#[path=\"02-b.rs\"] pub mod day02;\n\nfn"));
+        assert!(source.contains("(YEAR, 1),\n        Day::new::<day02::Solver>(YEAR, 2),\n"));
+        assert!(src.join("advent2017").join("02-b.rs").is_file());
+
+        assert!(scaffold(&root, &options(2017, 2, "b"), None).is_err());
+        assert!(scaffold(&root, &options(2017, 2, "c"), None).is_err());
+
+        scaffold(&root, &options(2015, 1, "a"), None).unwrap();
+
+        assert_eq!(fs::read_to_string(src.join("lib.rs")).unwrap(), "mod x;\nyears!(advent2015, advent2017);\n");
+        assert!(fs::read_to_string(src.join("advent2015").join("mod.rs")).unwrap()
+            .contains("Day::new::<day01::Solver>(YEAR, 1),"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+
+use Answer;
+use ParseError;
+
+/// A puzzle solution: an input parser plus one function for each part.
+///
+/// Each `adventYYYY::dayNN` module implements this on a unit `Solver` struct
+/// (through the `solution!` and `solution_both!` macros) by forwarding to its
+/// own `parse_input`, `part1` and `part2` (or `solve`) functions.
+///
+/// The lifetime is that of the raw input, since many parsed inputs borrow
+/// from it.
+pub trait Solution<'a> {
+    /// Parsed puzzle input.
+    type Input;
+
+    /// Whether `solve` does the work of both parts together, so that each
+    /// part costs as much as both. The runner then solves them in one go.
+    const SOLVES_BOTH: bool = false;
+
+    fn parse_input(input: &'a str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+
+    /// Solves both parts at once. Days which find both answers in the same
+    /// pass override this.
+    fn solve(input: &Self::Input) -> (Answer, Answer) {
+        (Self::part1(input), Self::part2(input))
+    }
+}
+
+/// A parsed puzzle input, with its type erased.
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// A registered solution for a given day, with its input type erased so that
+/// days can be stored and iterated over together.
+///
+/// Parsed inputs may borrow from the raw input, so erased parsing requires a
+/// `'static` input.
+#[derive(Copy, Clone)]
+pub struct Day {
+    pub year: usize,
+    pub day: usize,
+    parse: fn(&'static str) -> Result<Parsed, ParseError>,
+    part1: fn(&Parsed) -> Answer,
+    part2: fn(&Parsed) -> Answer,
+    solve: fn(&Parsed) -> (Answer, Answer),
+    solves_both: bool,
+}
+
+impl Day {
+    pub fn new<S>(year: usize, day: usize) -> Day
+        where S: Solution<'static>,
+              S::Input: Send + Sync + 'static,
+    {
+        Day {
+            year,
+            day,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+            solve: solve::<S>,
+            solves_both: S::SOLVES_BOTH,
+        }
+    }
+
+    pub fn parse_input(&self, input: &'static str) -> Result<Parsed, ParseError> {
+        (self.parse)(input)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `input` was not parsed by this same day.
+    pub fn part1(&self, input: &Parsed) -> Answer {
+        (self.part1)(input)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `input` was not parsed by this same day.
+    pub fn part2(&self, input: &Parsed) -> Answer {
+        (self.part2)(input)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `input` was not parsed by this same day.
+    pub fn solve(&self, input: &Parsed) -> (Answer, Answer) {
+        (self.solve)(input)
+    }
+
+    /// See [`Solution::SOLVES_BOTH`](trait.Solution.html#associatedconstant.SOLVES_BOTH).
+    pub fn solves_both(&self) -> bool {
+        self.solves_both
+    }
+}
+
+fn parse<S>(input: &'static str) -> Result<Parsed, ParseError>
+    where S: Solution<'static>,
+          S::Input: Send + Sync + 'static,
+{
+    S::parse_input(input).map(|input| Box::new(input) as Parsed)
+}
+
+fn downcast<S>(input: &Parsed) -> &S::Input
+    where S: Solution<'static>,
+          S::Input: Send + Sync + 'static,
+{
+    input.downcast_ref::<S::Input>()
+        .expect("Input was parsed by a different day")
+}
+
+fn part1<S>(input: &Parsed) -> Answer
+    where S: Solution<'static>,
+          S::Input: Send + Sync + 'static,
+{
+    S::part1(downcast::<S>(input))
+}
+
+fn part2<S>(input: &Parsed) -> Answer
+    where S: Solution<'static>,
+          S::Input: Send + Sync + 'static,
+{
+    S::part2(downcast::<S>(input))
+}
+
+fn solve<S>(input: &Parsed) -> (Answer, Answer)
+    where S: Solution<'static>,
+          S::Input: Send + Sync + 'static,
+{
+    S::solve(downcast::<S>(input))
+}
+
+/// Every registered solution, keyed by `(year, day)`.
+pub fn registry() -> BTreeMap<(usize, usize), Day> {
+    ::days()
+        .into_iter()
+        .map(|day| ((day.year, day.day), day))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn registry() {
+        let registry = super::registry();
+
+        assert!(registry.keys().cloned().eq((1..26).map(|day| (2017, day))));
+    }
+}
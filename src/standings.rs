@@ -0,0 +1,116 @@
+//! The `leaderboard` command: shows a private leaderboard, with how long each
+//! member took between both stars of each day.
+
+use advent_solutions::{ Downloader, Leaderboard };
+use cli::LeaderboardOptions;
+use { cache_dir, profile };
+
+/// A duration in seconds, in at most two units (e.g. `5m10s` or `1h02m`).
+fn format_delta(secs: u64) -> String {
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        3600..=86399 => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+        _ => format!("{}d{:02}h", secs / 86400, secs % 86400 / 3600),
+    }
+}
+
+/// Renders the ranked table, with a column for each of `days`.
+fn table(leaderboard: &Leaderboard, days: &[usize]) -> Vec<String> {
+    let ranked = leaderboard.ranked();
+
+    let name_width = ranked.iter()
+        .map(|member| member.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Name".len());
+
+    let mut header = format!("{:>4} {:>5} {:>5}  {:<width$}", "Rank", "Score", "Stars", "Name", width = name_width);
+
+    for day in days {
+        header.push_str(&format!(" {:>7}", day));
+    }
+
+    let mut lines = vec![header.trim_end().to_string()];
+
+    for (i, member) in ranked.iter().enumerate() {
+        let mut line = format!(
+            "{:>4} {:>5} {:>5}  {:<width$}",
+            i + 1,
+            member.local_score,
+            member.stars,
+            member.display_name(),
+            width = name_width,
+        );
+
+        for day in days {
+            let cell = match member.days.get(day) {
+                Some(completion) => completion.delta().map_or("*".to_string(), format_delta),
+                None => String::new(),
+            };
+
+            line.push_str(&format!(" {:>7}", cell));
+        }
+
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines
+}
+
+pub fn run(options: LeaderboardOptions) -> Result<bool, String> {
+    let profile = profile(&options.inputs)?;
+
+    let downloader = match profile {
+        Some(ref profile) => Downloader::with_profile(profile),
+        None => Downloader::new(),
+    };
+
+    let leaderboard = downloader
+        .unwrap_or_else(|e| {
+            eprintln!("{} (only cached leaderboards are available)", e);
+            Downloader::cache_only()
+        })
+        .cache_dir(cache_dir(&options.inputs, profile.as_ref()))
+        .leaderboard(options.year, options.id)
+        .map_err(|e| e.to_string())?;
+
+    let days = leaderboard.days().into_iter()
+        .filter(|day| options.days.as_ref().is_none_or(|days| days.contains(day)))
+        .collect::<Vec<_>>();
+
+    for line in table(&leaderboard, &days) {
+        println!("{}", line);
+    }
+
+    println!();
+    println!("Days show the time from the first to the second star (`*` if only the first).");
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deltas() {
+        assert_eq!(format_delta(45), "45s");
+        assert_eq!(format_delta(310), "5m10s");
+        assert_eq!(format_delta(3720), "1h02m");
+        assert_eq!(format_delta(2 * 86400 + 3 * 3600 + 59), "2d03h");
+    }
+
+    #[test]
+    fn ranked_table() {
+        let leaderboard = Leaderboard::parse(include_str!("../fixtures/leaderboard_2017.json")).unwrap();
+
+        assert_eq!(table(&leaderboard, &[1, 2]), vec![
+            "Rank Score Stars  Name                         1       2",
+            "   1    11     4  Alice                    2m00s  15m00s",
+            "   2     8     3  (anonymous user #1002)   1h00m       *",
+            "   3     1     1  Carol                        *",
+            "   4     0     0  Dave",
+        ]);
+    }
+}
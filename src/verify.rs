@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::{ fmt, fs, io };
+
+use Answer;
+
+/// Known answers for a year, read from a plain-text file with one
+/// `Day NN/P: answer` line per part (the same format the runner prints, so
+/// its output can be pasted as is). The verdict the runner prints after an
+/// answer is dropped, and so are lines of failed parts. Any other line is
+/// ignored.
+///
+/// ```
+/// # use advent_solutions::{ Answer, Answers, Verdict };
+/// let answers = Answers::parse("
+/// # Some comment
+/// Day 07/1: mkxke
+/// Day 07/2: 268 WRONG (expected 267)
+/// Day 08/1: FAILED: boom
+/// Day 25/2:  OK
+/// ");
+///
+/// assert_eq!(answers.verify(7, 1, &Answer::from("mkxke")), Verdict::Ok);
+/// assert_eq!(answers.verify(7, 2, &Answer::from(267usize)), Verdict::Wrong("268".to_string()));
+/// assert_eq!(answers.verify(8, 1, &Answer::from(4647isize)), Verdict::Unknown);
+/// assert_eq!(answers.verify(25, 2, &Answer::None), Verdict::Ok);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Answers(HashMap<(usize, usize), String>);
+
+/// The result of checking an answer against the known ones.
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub enum Verdict {
+    Ok,
+    /// Holds the expected answer.
+    Wrong(String),
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Verdict::Ok => write!(f, "OK"),
+            Verdict::Wrong(ref expected) => write!(f, "WRONG (expected {})", expected),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// The answer after the colon of a line, without the verdict the runner
+/// prints after it, or `None` if the runner printed a failure instead.
+fn answer(text: &str) -> Option<&str> {
+    let text = text.trim_end();
+
+    if text.trim_start().starts_with("FAILED: ") || text.trim_start().starts_with("TIMEOUT after ") {
+        return None;
+    }
+
+    let answer = match text.rfind(" WRONG (expected ") {
+        Some(i) if text.ends_with(')') => &text[..i],
+        _ => text.strip_suffix(" OK")
+            .or_else(|| text.strip_suffix(" UNKNOWN"))
+            .unwrap_or(text),
+    };
+
+    Some(answer.trim())
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Answers {
+        Answers(
+            input.lines()
+                .filter_map(|line| {
+                    let line = line.trim_start().trim_start_matches("Day ");
+                    let slash = line.find('/')?;
+                    let colon = line.find(':')?;
+
+                    let day = line[..slash].parse::<usize>().ok()?;
+                    let part = line.get(slash + 1..colon)?.parse::<usize>().ok()?;
+
+                    Some(((day, part), answer(&line[colon + 1..])?.to_string()))
+                })
+                .collect()
+        )
+    }
+
+    /// Reads the answers for `year` from `<dir>/<year>.txt`. A missing file
+    /// means no answer is known yet.
+    pub fn load<P: AsRef<Path>>(dir: P, year: usize) -> io::Result<Answers> {
+        match fs::read_to_string(dir.as_ref().join(format!("{}.txt", year))) {
+            Ok(input) => Ok(Answers::parse(&input)),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Compares `answer` with the known answer for `day`'s `part`, as printed.
+    pub fn verify(&self, day: usize, part: usize, answer: &Answer) -> Verdict {
+        match self.0.get(&(day, part)) {
+            Some(expected) if *expected == answer.to_string() => Verdict::Ok,
+            Some(expected) => Verdict::Wrong(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+}
@@ -0,0 +1,413 @@
+//! The `watch` command: runs a day again whenever its module or its input
+//! changes, showing what changed since the previous run.
+//!
+//! Every change rebuilds the binary with `cargo build`, which then does the
+//! run in a child process (`run --format csv`), so that a crash or an infinite
+//! loop only takes the child down.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{ Path, PathBuf };
+use std::process::{ Command, ExitStatus, Stdio };
+use std::time::{ Duration, Instant, SystemTime };
+use std::{ env, fs, io, thread };
+
+use cli::WatchOptions;
+use { cache_dir, profile, select_days };
+
+/// What the child reported for one part.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Outcome {
+    pub answer: String,
+    pub status: String,
+    pub error: String,
+    pub parse_time: Option<Duration>,
+    pub time: Option<Duration>,
+}
+
+/// Outcomes by part.
+type Outcomes = BTreeMap<usize, Outcome>;
+
+/// Splits a line of the runner's CSV output into its fields.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                fields.last_mut().unwrap().push('"');
+            },
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(String::new()),
+            c => fields.last_mut().unwrap().push(c),
+        }
+    }
+
+    fields
+}
+
+/// Reads the output of `run --format csv`.
+///
+/// Fields may contain newlines (e.g. in ASCII art answers), so this works
+/// on whole records rather than on lines.
+fn parse_outcomes(csv: &str) -> Outcomes {
+    let mut records = vec![];
+    let mut record = String::new();
+
+    for line in csv.lines() {
+        if !record.is_empty() {
+            record.push('\n');
+        }
+
+        record.push_str(line);
+
+        // An odd number of quotes means a quoted field goes on.
+        if record.matches('"').count() % 2 != 1 {
+            records.push(csv_fields(&record));
+            record.clear();
+        }
+    }
+
+    let nanos = |field: &str| field.parse::<u64>().ok().map(Duration::from_nanos);
+
+    records.into_iter()
+        .skip(1)
+        .filter(|fields| fields.len() == 10)
+        .filter_map(|fields| {
+            let part = fields[2].parse::<usize>().ok()?;
+
+            Some((part, Outcome {
+                answer: fields[3].clone(),
+                status: fields[7].clone(),
+                error: fields[9].clone(),
+                parse_time: nanos(&fields[5]),
+                time: nanos(&fields[6]),
+            }))
+        })
+        .collect()
+}
+
+/// `time`, and how it compares to `previous` if that is known.
+fn timing(time: Option<Duration>, previous: Option<Duration>) -> String {
+    match (time, previous) {
+        (Some(time), Some(previous)) if previous.as_nanos() > 0 => {
+            let delta = (time.as_nanos() as f64 / previous.as_nanos() as f64 - 1.0) * 100.0;
+            format!("{:?} ({:+.1}%)", time, delta)
+        },
+        (Some(time), _) => format!("{:?}", time),
+        (None, _) => "-".to_string(),
+    }
+}
+
+/// Describes each part of `outcomes`, along with what changed since
+/// `previous`.
+fn diff(day: usize, previous: &Outcomes, outcomes: &Outcomes) -> Vec<String> {
+    outcomes.iter()
+        .map(|(&part, outcome)| {
+            let before = previous.get(&part);
+
+            let mut line = if outcome.error.is_empty() {
+                format!("Day {:02}/{}: {} {}", day, part, outcome.answer, outcome.status)
+            } else {
+                format!("Day {:02}/{}: {}: {}", day, part, outcome.status, outcome.error)
+            };
+
+            if let Some(before) = before {
+                if before.answer != outcome.answer && !before.answer.is_empty() {
+                    line.push_str(&format!(" (was {})", before.answer));
+                } else if before.status != outcome.status {
+                    line.push_str(&format!(" (was {})", before.status));
+                }
+            }
+
+            line.push_str(&format!(
+                "; parse {}, part {}",
+                timing(outcome.parse_time, before.and_then(|x| x.parse_time)),
+                timing(outcome.time, before.and_then(|x| x.time)),
+            ));
+
+            line
+        })
+        .collect()
+}
+
+/// Finds `src/adventYYYY/NN-name.rs`.
+fn module_path(year: usize, day: usize) -> Result<PathBuf, String> {
+    let dir = Path::new("src").join(format!("advent{}", year));
+    let prefix = format!("{:02}-", day);
+
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("{}: {} (run from the repository root)", dir.display(), e))?;
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".rs"))
+        })
+        .ok_or_else(|| format!("No module for day {} in {}", day, dir.display()))
+}
+
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths.iter()
+        .map(|path| fs::metadata(path).and_then(|x| x.modified()).ok())
+        .collect()
+}
+
+/// Rebuilds this binary, with the same profile.
+fn build() -> io::Result<ExitStatus> {
+    let mut cargo = Command::new("cargo");
+    cargo.args(["build", "--quiet"]);
+
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+
+    cargo.status()
+}
+
+/// The `run` arguments for the watched day.
+fn run_args(options: &WatchOptions, year: usize, day: usize) -> Vec<String> {
+    let mut args = vec![
+        "run".to_string(),
+        "--year".to_string(), year.to_string(),
+        "--day".to_string(), day.to_string(),
+        "--format".to_string(), "csv".to_string(),
+        "--timeout".to_string(), options.timeout.as_secs().to_string(),
+    ];
+
+    if let Some(part) = options.selection.part {
+        args.extend(vec!["--part".to_string(), part.to_string()]);
+    }
+
+    if let Some(ref answers_dir) = options.answers_dir {
+        args.extend(vec!["--answers".to_string(), answers_dir.clone()]);
+    }
+
+    let inputs = &options.inputs;
+
+    if let Some(ref input) = inputs.input {
+        args.extend(vec!["--input".to_string(), input.clone()]);
+    }
+
+    if let Some(ref cache_dir) = inputs.cache_dir {
+        args.extend(vec!["--cache".to_string(), cache_dir.clone()]);
+    }
+
+    if let Some(ref profile) = inputs.profile {
+        args.extend(vec!["--profile".to_string(), profile.clone()]);
+    }
+
+    args
+}
+
+/// Runs `command`, killing it after `timeout`. Returns `None` if it had to be
+/// killed, or else how it exited and its output.
+fn run_child(mut command: Command, timeout: Duration) -> io::Result<Option<(ExitStatus, String)>> {
+    let mut child = command.stdout(Stdio::piped()).spawn()?;
+
+    // Read as it goes, so that the child never blocks on a full pipe.
+    let mut stdout = child.stdout.take().unwrap();
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).map(|_| output)
+    });
+
+    let start = Instant::now();
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if start.elapsed() > timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let output = reader.join().unwrap()?;
+
+    Ok(Some((status, output)))
+}
+
+pub fn run(options: WatchOptions) -> Result<bool, String> {
+    // If no --year was given, watch the latest one with that day.
+    let (year, day) = *select_days(&options.selection)?
+        .keys()
+        .next_back()
+        .ok_or("No day selected")?;
+
+    // The runs would all fail on a bad profile, even with --input.
+    let profile = profile(&options.inputs)?;
+
+    let input_path = match options.inputs.input {
+        Some(ref path) if path == "-" => return Err("watch cannot read the input from stdin".to_string()),
+        Some(ref path) => PathBuf::from(path),
+        None => cache_dir(&options.inputs, profile.as_ref())
+            .join(year.to_string())
+            .join(format!("{:02}", day)),
+    };
+
+    let paths = vec![module_path(year, day)?, input_path];
+
+    // Once rebuilt, the path may no longer point to this process' binary, but
+    // it will point to the new one.
+    let exe = env::current_exe().map_err(|e| format!("Error finding the binary: {}", e))?;
+
+    eprintln!(
+        "Watching {} and {} (Ctrl+C to stop)",
+        paths[0].display(),
+        paths[1].display()
+    );
+
+    let mut last_modified = vec![];
+    let mut previous = Outcomes::new();
+
+    loop {
+        let now_modified = modified(&paths);
+
+        if now_modified == last_modified {
+            thread::sleep(options.interval);
+            continue;
+        }
+
+        let changed = paths.iter()
+            .zip(&now_modified)
+            .enumerate()
+            .filter(|&(i, (_, time))| last_modified.get(i) != Some(time))
+            .map(|(_, (path, _))| path.display().to_string())
+            .collect::<Vec<_>>();
+
+        last_modified = now_modified;
+
+        println!();
+        println!("--- {}", changed.join(", "));
+
+        match build() {
+            Ok(status) if status.success() => {},
+            Ok(_) => {
+                println!("Build FAILED");
+                continue;
+            },
+            Err(e) => return Err(format!("Error running cargo: {}", e)),
+        }
+
+        let mut child = Command::new(&exe);
+        child.args(run_args(&options, year, day));
+
+        // The child times out steps on its own, and parsing and then a part
+        // may both take up to the timeout. Only kill it if something else
+        // (like a download) hangs.
+        let kill_after = options.timeout * 3;
+
+        match run_child(child, kill_after) {
+            Ok(None) => println!("Day {:02}: KILLED after {:?}", day, kill_after),
+            Ok(Some((status, output))) => {
+                let outcomes = parse_outcomes(&output);
+
+                for line in diff(day, &previous, &outcomes) {
+                    println!("{}", line);
+                }
+
+                // 1 only means that some part failed, which is reported above.
+                if !status.success() && status.code() != Some(1) {
+                    println!("Day {:02}: CRASHED ({})", day, status);
+                }
+
+                if !outcomes.is_empty() {
+                    previous = outcomes;
+                }
+            },
+            Err(e) => return Err(format!("Error running {}: {}", exe.display(), e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cli::{ InputOptions, Selection };
+
+    fn ms(x: u64) -> Option<Duration> {
+        Some(Duration::from_millis(x))
+    }
+
+    #[test]
+    fn outcomes() {
+        let csv = "\
+year,day,part,answer,type,parse_ns,time_ns,status,expected,error
+2017,3,1,438,unsigned,1000000,2000000,OK,,
+2017,3,2,\"a,\"\"b\"\"
+c\",string,1000000,,FAILED,,\"boom, really\"
+";
+
+        let outcomes = parse_outcomes(csv);
+
+        assert_eq!(outcomes.len(), 2);
+        assert_eq!(outcomes[&1], Outcome {
+            answer: "438".to_string(),
+            status: "OK".to_string(),
+            error: String::new(),
+            parse_time: ms(1),
+            time: ms(2),
+        });
+        assert_eq!(outcomes[&2].answer, "a,\"b\"\nc");
+        assert_eq!(outcomes[&2].error, "boom, really");
+        assert_eq!(outcomes[&2].time, None);
+    }
+
+    #[test]
+    fn changes() {
+        let outcome = |answer: &str, status: &str, time| Outcome {
+            answer: answer.to_string(),
+            status: status.to_string(),
+            error: String::new(),
+            parse_time: ms(1),
+            time,
+        };
+
+        let mut previous = Outcomes::new();
+        previous.insert(1, outcome("437", "WRONG", ms(4)));
+
+        let mut outcomes = Outcomes::new();
+        outcomes.insert(1, outcome("438", "OK", ms(2)));
+        outcomes.insert(2, outcome("", "FAILED", None));
+        outcomes.get_mut(&2).unwrap().error = "boom".to_string();
+
+        assert_eq!(diff(3, &previous, &outcomes), vec![
+            "Day 03/1: 438 OK (was 437); parse 1ms (+0.0%), part 2ms (-50.0%)",
+            "Day 03/2: FAILED: boom; parse 1ms, part -",
+        ]);
+
+        assert_eq!(diff(3, &Outcomes::new(), &previous), vec![
+            "Day 03/1: 437 WRONG; parse 1ms, part 4ms",
+        ]);
+    }
+
+    #[test]
+    fn child_args() {
+        let mut options = WatchOptions {
+            selection: Selection::default(),
+            inputs: InputOptions::default(),
+            interval: Duration::from_millis(500),
+            answers_dir: Some("answers/alice".to_string()),
+            timeout: Duration::from_secs(5),
+        };
+        options.selection.part = Some(2);
+        options.inputs.profile = Some("alice".to_string());
+
+        assert_eq!(run_args(&options, 2017, 3), vec![
+            "run", "--year", "2017", "--day", "3", "--format", "csv", "--timeout", "5",
+            "--part", "2", "--answers", "answers/alice", "--profile", "alice",
+        ]);
+    }
+}